@@ -23,6 +23,10 @@ def load_all():
     if not dfs:
         raise SystemExit("No input CSVs found. Expected: " + ", ".join(INPUTS))
     df = pd.concat(dfs, ignore_index=True)
+    # the Rust harness appends summary_<impl> rows and can lead with __meta__ rows; keep per-rep data only
+    if "rep_id" in df.columns:
+        df = df[df["rep_id"].astype(str) != "summary"]
+    df = df[df["impl_name"] != "__meta__"]
     for col in ["N","seed","rep_id","ops_in_run","total_time_ns","init_time_ns_if_recorded"]:
         if col in df.columns: df[col] = pd.to_numeric(df[col], errors="coerce")
     df["ns_per_op"] = pd.to_numeric(df["ns_per_op"], errors="coerce")
@@ -0,0 +1,42 @@
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn git(args: &[&str]) -> Option<String> {
+    let out = Command::new("git").args(args).output().ok()?;
+    if !out.status.success() { return None; }
+    String::from_utf8(out.stdout).ok()
+}
+
+fn main() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc).arg("--version").output().ok()
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=RUSTC_VERSION={}", version);
+
+    let hash = git(&["rev-parse", "HEAD"]).map(|s| s.trim().to_string()).unwrap_or_else(|| "unknown".to_string());
+    let dirty = match git(&["status", "--porcelain"]) {
+        Some(s) => if s.trim().is_empty() { "false" } else { "true" },
+        None => "unknown",
+    };
+    println!("cargo:rustc-env=GIT_COMMIT_HASH={}", hash);
+    println!("cargo:rustc-env=GIT_DIRTY={}", dirty);
+    // .git is outside the package, so commits and staging do not re-run this script by default;
+    // naming any path also drops cargo's "any package file" rule, so list the package inputs too
+    if let Some(dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        let dir = std::path::Path::new(dir.trim());
+        let current_ref = git(&["symbolic-ref", "-q", "HEAD"]).map(|r| dir.join(r.trim()));
+        for p in [Some(dir.join("HEAD")), Some(dir.join("index")), current_ref].into_iter().flatten() {
+            // a missing path (e.g. a ref that only lives in packed-refs) would force a re-run on every build
+            if p.exists() { println!("cargo:rerun-if-changed={}", p.display()); }
+        }
+    }
+    for p in ["build.rs", "Cargo.toml", "src"] { println!("cargo:rerun-if-changed={}", p); }
+
+    let opt_level = std::env::var("OPT_LEVEL").unwrap_or_default();
+    println!("cargo:rustc-env=BUILD_PROFILE={}", if opt_level == "0" { "debug" } else { "release" });
+
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", secs);
+}
@@ -1,28 +1,193 @@
 // rust_benchmark/src/main.rs
 // Usage:
 //   cargo run --release --manifest-path rust_benchmark/Cargo.toml -- --Ns 1k,10k,100k,1m,10m,100m --reps 3 --seed 42 --outfile rust-results.csv
+//   `--format csv|json|jsonl` selects the output encoding (csv by default);
+//   `--format sqlite` writes a `benchmark_results` table and needs `--features sqlite`
+//   summary rows (rep_id = "summary") with mean/stddev/min/max of total_time_ns are appended
+//   (plus p50/p95/p99 of ns_per_op once there are at least 5 reps); `--no-summary` drops them
+//   `throughput_gbs` is ops * bytes_per_op / elapsed, with bytes counted at the impl's element width
+//     (backing bytes / N); -1 where bytes per op is ill-defined
+//   `relocations_count` counts backing-storage reallocations (e.g. hashmap rehashes) and
+//   `conversions_count` counts i64 <-> element casts in the typed impls, both per rep
+//   `--peak-bw-gbs <f64>` (e.g. a measured STREAM figure) fills `pct_of_peak_bandwidth`
+//   `--meta` writes leading impl_name = "__meta__" rows (key in `scenario`, value in `meta_value`)
+//   describing the host, toolchain, build and git revision
+//   `--outfile-template "results-%Y%m%d-%H%M%S.csv"` replaces `--outfile`, expanding %Y %m %d %H %M %S
+//     from the UTC start time
+//   `--split-by-scenario --outdir <dir>` writes `<dir>/<scenario>.csv` per scenario (the extension
+//     follows `--format`) plus `__meta__.csv` for meta rows, instead of one `--outfile`
+//   `--output-append` adds rows to an existing csv/jsonl file instead of truncating it
+//   `--Ns` sizes take k/m/g/t (powers of 1000), b (no multiplier), or a trailing p for powers of 1024 (1kp = 1024)
+//   `--Ns-powers-of-2 1k..1g` appends every power of two in the inclusive range to the `--Ns` list
+//   `--Ns-auto` replaces `--Ns` with sizes derived from the detected L1/L2/L3 caches and RAM
+//   `--strict` rejects malformed `--Ns` tokens instead of skipping them
+//   `--seeds 1,2,3` runs every (N, scenario, impl, rep) once per seed instead of the single `--seed`
+//   `--reps-adaptive [--cv-threshold 0.02] [--reps-max 20]` keeps adding reps (at least --reps)
+//   until the CV of ns_per_op drops below the threshold; the summary `rep_count` shows how many ran
+//   `--min-runtime-ms <ms>` keeps adding reps (at least --reps, at most --reps-max) until their summed
+//     total_time_ns reaches the budget; combines with --reps-adaptive (both must be satisfied). A
+//     group that hits --reps-max first warns and gets a `min_runtime_shortfall` meta row
+//   `--cooldown-ms <ms>` sleeps between consecutive reps (outside the timed region)
+//   `--warmup <k>` runs each scenario k extra times, unrecorded, before the measured reps
+//   `--timeout-ms <ms>` stops repeating a run once a rep exceeds the budget (sentinel row with total_time_ns = -1)
+//   `--scenarios INIT_ONLY,MIXED_` runs only the listed scenarios; a trailing `_` or `*` matches by prefix
+//   `--scenarios-file <path>` adds one scenario name or pattern per line (`#` starts a comment line)
+//   `--impls rust_vec_i64,rust_hashmap_i64` runs only the impls with those names;
+//     rust_cacheline_padded_i64 (64 bytes per element) only runs when named here
+//   `--list-scenarios` prints the default scenario names and exits
+//   `--list-impls` prints the impl names compiled into this build and exits
+//   `--dry-run` prints how many benchmarks the arguments expand to and exits without writing
+//   `--sort-queries <m>` sets the number of binary searches in SORT_THEN_SEARCH (default min(1M, N))
+//   `--zigzag-passes <p>` sets how many alternating-direction passes WRITE_ZIGZAG makes (default 4)
+//   `--tmpdir <dir>` selects where the file-backed mmap impl creates its scratch file
+//   `--pin-cpu <core>` pins the process to one CPU before benchmarking (Linux only)
+//   `--mlocked` mlocks each impl's backing memory before it is benchmarked
+//   `--hugepages` madvises each impl's memory with MADV_HUGEPAGE (Linux) and records the peak
+//     AnonHugePages as a `hugepages_granted_kb` meta row. Neither covers rust_growing_vec_i64, whose
+//     init reallocates; it runs unlocked and unadvised with a warning
+//   `--prefetch` issues a software prefetch 8 indices ahead in READ_UNWRITTEN and WRITE_RANDOM (x86_64)
+//   `--compare-csv <file>` prints mean total_time_ns / ops_in_run against a baseline CSV per (impl,
+//     scenario, N, seed) and exits 1 if any run is slower by more than `--regression-threshold`
+//     (default 0.05), or if no run matched the baseline at all
+//   `--progress` prints `[run/total] SCENARIO N=<n> rep=<r>/<reps>` to stderr before each rep
+//     (rewritten in place on a terminal)
+//   once a rep has finished, an `ETA: HH:MM:SS` projected from the mean time per completed rep is
+//     appended to the progress line, or shown on its own line in place when stderr is a terminal
+//   `--emit-intermediate` prints `<impl> <scenario> N=<n> seed=<s> rep=<r>: <x> ns/op` to stdout per rep
+//   `--config <file.toml>` reads flags from a TOML file keyed by flag name (`Ns = ["1k", "100k"]`,
+//     `reps = 5`, `scenarios = ["WRITE_SEQUENTIAL"]`, `no-summary = true`); command-line flags win
+//   every flag can also come from a BENCH_<FLAG> env var (BENCH_NS, BENCH_REPS, BENCH_NO_SUMMARY=1),
+//     lowest priority: config file and command line override it
+//   `--seed-range 42..52` runs seeds 42 through 51 (half-open, at most 1000 seeds)
+//   `--verify` checks each WRITE_SEQUENTIAL result (arr[i] == i) after the rep and exits 1 on a mismatch
+//   `--checksum` XORs every element after each rep into a `checksum` column and warns when reps of
+//     the same run disagree
+//   `--clock-overhead` measures the minimum gap between back-to-back `Instant::now()` calls first,
+//     writes it as a `clock_overhead_ns` meta row and warns for runs whose mean total_time_ns per rep
+//     is within 10x of it
+//   `--repeat-inner <k>` runs the timed loop k times per recorded time (ops_in_run = k x m),
+//     amortising timer overhead when ns_per_op is near 1; honoured by READ_UNWRITTEN, PREFILL_RANDOM,
+//     READ_HOT_SEGMENT, READ_AFTER_WRITE, WRITE_THEN_READ_SAME_INDICES, WRITE_SEQUENTIAL,
+//     REVERSE_SEQUENTIAL, WRITE_RANDOM, MIXED_* and REDUCE_SUM
+//   `--no-black-box` builds the scenarios with `black_box` as the identity, chosen once per run
+//     rather than per call. Expect scenarios working on the slice view (REDUCE_SUM, COPY_ARRAY) to
+//     get faster as the optimiser vectorises or folds their loops, and the per-op `dyn ArrayImpl`
+//     scenarios to move only by noise, since the virtual call already hides the access; a large
+//     gap means the default figure leans on black_box to stay honest
+//   `--threads <n>` sizes the rayon pool used by the parallel scenarios
+//   add `--features unsafe_impl` to also benchmark the unchecked raw-pointer impl,
+//   `--features slab_impl` for the bumpalo arena impl, `--features rayon` for the multi-threaded scenarios,
+//   `--features numa` for the libnuma interleaved/local impls (Linux, links -lnuma)
 
 use chrono::Utc;
 use csv::Writer;
-use rand::{Rng, SeedableRng};
-use rand::rngs::StdRng;
 use std::env;
-use std::hint::black_box;
 use std::time::Instant;
 
+mod scenarios;
+use scenarios::{select_scenarios, ScenarioOpts};
+
 fn now_iso() -> String { Utc::now().to_rfc3339() }
 
+// Only the six date/time fields are expanded; any other `%` sequence is kept verbatim.
+fn expand_outfile_template(template: &str, at: chrono::DateTime<Utc>) -> String {
+    ["%Y", "%m", "%d", "%H", "%M", "%S"].iter()
+        .fold(template.to_string(), |s, &spec| s.replace(spec, &at.format(spec).to_string()))
+}
+
 trait ArrayImpl {
     fn name(&self) -> &'static str;
     fn init(&mut self, v: i64) -> i64;
     fn read(&self, i: usize) -> i64;
     fn write(&mut self, i: usize, v: i64);
+    // contiguous view for scenarios built on slice primitives; `None` skips those scenarios
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { None }
+    // the owning Vec for scenarios that change its length; they must restore len n before returning
+    fn vec_mut(&mut self) -> Option<&mut Vec<i64>> { None }
+    // whether every index below n survives a write/read round trip; pointer-chasing scenarios need it
+    fn holds_indices(&self, _n: usize) -> bool { true }
+    // reallocations of the backing storage and i64 <-> element casts since construction;
+    // these fill relocations_count and conversions_count
+    fn relocations(&self) -> u64 { 0 }
+    fn conversions(&self) -> u64 { 0 }
+    // false when `init` replaces the backing memory, so a --mlocked/--hugepages region taken
+    // before the run would cover freed memory
+    fn stable_region(&self) -> bool { true }
+    // backing memory as (ptr, bytes) for mlock/madvise; defaults to the i64 slice view
+    fn region(&mut self) -> Option<(*mut u8, usize)> {
+        self.slice_mut().map(|a| (a.as_mut_ptr() as *mut u8, std::mem::size_of_val(a)))
+    }
+    // shared-access view for the multi-threaded atomic scenarios
+    #[cfg(feature = "rayon")]
+    fn atomics(&self) -> Option<&[std::sync::atomic::AtomicI64]> { None }
+}
+
+// Element types for `TypedVecImpl`; the harness stays i64 and values are cast at the boundary.
+trait Elem: Copy + Default {
+    const NAME: &'static str;
+    // largest n for which all of 0..n is exactly representable
+    const MAX_EXACT: u64;
+    // false when the element is i64 itself and nothing is cast
+    const CONVERTS: bool = true;
+    fn from_i64(v: i64) -> Self;
+    fn to_i64(self) -> i64;
+    fn i64_slice(_s: &mut [Self]) -> Option<&mut [i64]> { None }
+    fn i64_vec(_v: &mut Vec<Self>) -> Option<&mut Vec<i64>> { None }
+}
+impl Elem for i64 {
+    const NAME: &'static str = "rust_vec_i64";
+    const MAX_EXACT: u64 = u64::MAX;
+    const CONVERTS: bool = false;
+    fn from_i64(v: i64) -> Self { v }
+    fn to_i64(self) -> i64 { self }
+    fn i64_slice(s: &mut [Self]) -> Option<&mut [i64]> { Some(s) }
+    fn i64_vec(v: &mut Vec<Self>) -> Option<&mut Vec<i64>> { Some(v) }
+}
+macro_rules! cast_elem {
+    ($t:ty, $name:expr, $max:expr) => {
+        impl Elem for $t {
+            const NAME: &'static str = $name;
+            const MAX_EXACT: u64 = $max;
+            fn from_i64(v: i64) -> Self { v as $t }
+            fn to_i64(self) -> i64 { self as i64 }
+        }
+    };
 }
+cast_elem!(f64, "rust_vec_f64", 1 << 53);
+cast_elem!(f32, "rust_vec_f32", 1 << 24);
+cast_elem!(i32, "rust_vec_i32", 1 << 31);
+cast_elem!(u8, "rust_vec_u8", 1 << 8);
 
-struct VecImpl { n: usize, a: Vec<i64> }
-impl VecImpl { fn new(n: usize) -> Self { Self { n, a: vec![0; n] } } }
-impl ArrayImpl for VecImpl {
-    fn name(&self) -> &'static str { "rust_vec_i64" }
+// `conv` is a Cell because reads convert through &self.
+struct TypedVecImpl<T: Elem> { n: usize, a: Vec<T>, conv: std::cell::Cell<u64> }
+impl<T: Elem> TypedVecImpl<T> {
+    fn new(n: usize) -> Self { Self { n, a: vec![T::default(); n], conv: Default::default() } }
+    fn count_conversion(&self) { if T::CONVERTS { self.conv.set(self.conv.get() + 1); } }
+}
+impl<T: Elem> ArrayImpl for TypedVecImpl<T> {
+    fn name(&self) -> &'static str { T::NAME }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        let x = T::from_i64(v);
+        self.count_conversion();
+        for i in 0..self.n { self.a[i] = x; }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { self.count_conversion(); self.a[i].to_i64() }
+    fn write(&mut self, i: usize, v: i64) { self.count_conversion(); self.a[i] = T::from_i64(v); }
+    fn conversions(&self) -> u64 { self.conv.get() }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { T::i64_slice(&mut self.a) }
+    fn vec_mut(&mut self) -> Option<&mut Vec<i64>> { T::i64_vec(&mut self.a) }
+    fn holds_indices(&self, n: usize) -> bool { n as u64 <= T::MAX_EXACT }
+    fn region(&mut self) -> Option<(*mut u8, usize)> {
+        Some((self.a.as_mut_ptr() as *mut u8, std::mem::size_of_val(&self.a[..])))
+    }
+}
+
+struct BoxedSliceImpl { n: usize, a: Box<[i64]> }
+impl BoxedSliceImpl { fn new(n: usize) -> Self { Self { n, a: vec![0; n].into_boxed_slice() } } }
+impl ArrayImpl for BoxedSliceImpl {
+    fn name(&self) -> &'static str { "rust_boxed_slice_i64" }
     fn init(&mut self, v: i64) -> i64 {
         let t0 = Instant::now();
         for i in 0..self.n { self.a[i] = v; }
@@ -30,153 +195,1510 @@ impl ArrayImpl for VecImpl {
     }
     fn read(&self, i: usize) -> i64 { self.a[i] }
     fn write(&mut self, i: usize, v: i64) { self.a[i] = v; }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(&mut self.a) }
+}
+
+// Rebuilt by push from capacity 1 on every init, so init pays the amortised regrowth that the
+// pre-sized vec avoids; `relocs` is the number of capacity changes in the latest init.
+struct GrowingVecImpl { target_n: usize, a: Vec<i64>, relocs: u64 }
+impl GrowingVecImpl {
+    fn new(n: usize) -> Self {
+        let mut g = Self { target_n: n, a: Vec::new(), relocs: 0 };
+        g.init(0);
+        g
+    }
+}
+impl ArrayImpl for GrowingVecImpl {
+    fn name(&self) -> &'static str { "rust_growing_vec_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        self.a = Vec::with_capacity(1);
+        self.relocs = 0;
+        for _ in 0..self.target_n {
+            let cap = self.a.capacity();
+            self.a.push(v);
+            if self.a.capacity() != cap { self.relocs += 1; }
+        }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { self.a[i] }
+    fn write(&mut self, i: usize, v: i64) { self.a[i] = v; }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(&mut self.a) }
+    fn vec_mut(&mut self) -> Option<&mut Vec<i64>> { Some(&mut self.a) }
+    fn relocations(&self) -> u64 { self.relocs }
+    fn stable_region(&self) -> bool { false }
+}
+
+#[cfg(feature = "unsafe_impl")]
+struct UnsafePtrImpl { n: usize, layout: std::alloc::Layout, p: *mut i64 }
+#[cfg(feature = "unsafe_impl")]
+impl UnsafePtrImpl {
+    fn new(n: usize) -> Self {
+        // zero-sized layouts are not allowed by alloc, so always reserve at least one slot
+        let layout = std::alloc::Layout::array::<i64>(n.max(1)).expect("layout overflow");
+        let p = unsafe { std::alloc::alloc_zeroed(layout) } as *mut i64;
+        if p.is_null() { std::alloc::handle_alloc_error(layout); }
+        Self { n, layout, p }
+    }
+}
+#[cfg(feature = "unsafe_impl")]
+impl Drop for UnsafePtrImpl {
+    fn drop(&mut self) { unsafe { std::alloc::dealloc(self.p as *mut u8, self.layout) } }
+}
+#[cfg(feature = "unsafe_impl")]
+impl ArrayImpl for UnsafePtrImpl {
+    fn name(&self) -> &'static str { "rust_raw_ptr_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { unsafe { *self.p.add(i) = v; } }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { unsafe { *self.p.add(i) } }
+    fn write(&mut self, i: usize, v: i64) { unsafe { *self.p.add(i) = v; } }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(unsafe { std::slice::from_raw_parts_mut(self.p, self.n) }) }
 }
 
-fn parse_sizes(s: &str) -> Vec<usize> {
+// Fresh anonymous mapping: pages are only faulted in on first touch, so `init`
+// measures the page-fault storm along with the stores.
+#[cfg(unix)]
+struct MmapImpl { n: usize, len: usize, p: *mut i64 }
+#[cfg(unix)]
+impl MmapImpl {
+    fn new(n: usize) -> Self {
+        let len = std::cmp::max(1, n) * std::mem::size_of::<i64>();
+        let p = unsafe {
+            libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ | libc::PROT_WRITE,
+                       libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+        };
+        if p == libc::MAP_FAILED { panic!("mmap of {} bytes failed: {}", len, std::io::Error::last_os_error()); }
+        Self { n, len, p: p as *mut i64 }
+    }
+}
+#[cfg(unix)]
+impl Drop for MmapImpl {
+    fn drop(&mut self) { unsafe { libc::munmap(self.p as *mut libc::c_void, self.len); } }
+}
+#[cfg(unix)]
+impl ArrayImpl for MmapImpl {
+    fn name(&self) -> &'static str { "rust_mmap_anon_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { unsafe { *self.p.add(i) = v; } }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { assert!(i < self.n); unsafe { *self.p.add(i) } }
+    fn write(&mut self, i: usize, v: i64) { assert!(i < self.n); unsafe { *self.p.add(i) = v; } }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(unsafe { std::slice::from_raw_parts_mut(self.p, self.n) }) }
+}
+
+// Shared mapping of a temp file in `dir`, pre-extended with `set_len`; the file is removed on drop.
+#[cfg(unix)]
+struct MmapFileImpl { n: usize, len: usize, p: *mut i64, _file: tempfile::NamedTempFile }
+#[cfg(unix)]
+impl MmapFileImpl {
+    fn new(n: usize, dir: &std::path::Path) -> Self {
+        use std::os::unix::io::AsRawFd;
+        let len = std::cmp::max(1, n) * std::mem::size_of::<i64>();
+        let file = tempfile::NamedTempFile::new_in(dir)
+            .unwrap_or_else(|e| panic!("cannot create temp file in {}: {}", dir.display(), e));
+        file.as_file().set_len(len as u64).unwrap_or_else(|e| panic!("cannot extend {}: {}", file.path().display(), e));
+        let p = unsafe {
+            libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ | libc::PROT_WRITE,
+                       libc::MAP_SHARED, file.as_file().as_raw_fd(), 0)
+        };
+        if p == libc::MAP_FAILED { panic!("mmap of {} failed: {}", file.path().display(), std::io::Error::last_os_error()); }
+        Self { n, len, p: p as *mut i64, _file: file }
+    }
+}
+#[cfg(unix)]
+impl Drop for MmapFileImpl {
+    fn drop(&mut self) { unsafe { libc::munmap(self.p as *mut libc::c_void, self.len); } }
+}
+#[cfg(unix)]
+impl ArrayImpl for MmapFileImpl {
+    fn name(&self) -> &'static str { "rust_mmap_file_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { unsafe { *self.p.add(i) = v; } }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { assert!(i < self.n); unsafe { *self.p.add(i) } }
+    fn write(&mut self, i: usize, v: i64) { assert!(i < self.n); unsafe { *self.p.add(i) = v; } }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(unsafe { std::slice::from_raw_parts_mut(self.p, self.n) }) }
+}
+
+// The map starts empty, so filling it rehashes; `relocs` counts the capacity changes.
+struct HashMapImpl { n: usize, map: std::collections::HashMap<usize, i64>, relocs: u64 }
+impl HashMapImpl {
+    fn new(n: usize) -> Self { Self { n, map: std::collections::HashMap::new(), relocs: 0 } }
+    fn insert(&mut self, i: usize, v: i64) {
+        let cap = self.map.capacity();
+        self.map.insert(i, v);
+        if self.map.capacity() != cap { self.relocs += 1; }
+    }
+}
+impl ArrayImpl for HashMapImpl {
+    fn name(&self) -> &'static str { "rust_hashmap_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        self.map.clear();
+        for i in 0..self.n { self.insert(i, v); }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { *self.map.get(&i).unwrap_or(&0) }
+    fn write(&mut self, i: usize, v: i64) { self.insert(i, v); }
+    fn relocations(&self) -> u64 { self.relocs }
+}
+
+struct BTreeMapImpl { n: usize, map: std::collections::BTreeMap<usize, i64> }
+impl BTreeMapImpl { fn new(n: usize) -> Self { Self { n, map: std::collections::BTreeMap::new() } } }
+impl ArrayImpl for BTreeMapImpl {
+    fn name(&self) -> &'static str { "rust_btreemap_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        self.map.clear();
+        for i in 0..self.n { self.map.insert(i, v); }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { *self.map.get(&i).unwrap_or(&0) }
+    fn write(&mut self, i: usize, v: i64) { self.map.insert(i, v); }
+}
+
+struct VecDequeImpl { n: usize, dq: std::collections::VecDeque<i64> }
+impl VecDequeImpl { fn new(n: usize) -> Self { Self { n, dq: vec![0; n].into() } } }
+impl ArrayImpl for VecDequeImpl {
+    fn name(&self) -> &'static str { "rust_vecdeque_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { self.dq[i] = v; }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { self.dq[i] }
+    fn write(&mut self, i: usize, v: i64) { self.dq[i] = v; }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(self.dq.make_contiguous()) }
+}
+
+// Same bounds-checked access as `TypedVecImpl<i64>`, but the buffer starts on a 64-byte cacheline.
+struct AlignedVecImpl { n: usize, layout: std::alloc::Layout, p: *mut i64 }
+impl AlignedVecImpl {
+    fn new(n: usize) -> Self {
+        let layout = std::alloc::Layout::from_size_align(std::cmp::max(1, n) * std::mem::size_of::<i64>(), 64)
+            .expect("layout overflow");
+        let p = unsafe { std::alloc::alloc_zeroed(layout) } as *mut i64;
+        if p.is_null() { std::alloc::handle_alloc_error(layout); }
+        Self { n, layout, p }
+    }
+    fn as_slice(&self) -> &[i64] { unsafe { std::slice::from_raw_parts(self.p, self.n) } }
+    fn as_mut_slice(&mut self) -> &mut [i64] { unsafe { std::slice::from_raw_parts_mut(self.p, self.n) } }
+}
+impl Drop for AlignedVecImpl {
+    fn drop(&mut self) { unsafe { std::alloc::dealloc(self.p as *mut u8, self.layout) } }
+}
+impl ArrayImpl for AlignedVecImpl {
+    fn name(&self) -> &'static str { "rust_aligned64_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        let a = self.as_mut_slice();
+        for x in a.iter_mut() { *x = v; }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { self.as_slice()[i] }
+    fn write(&mut self, i: usize, v: i64) { self.as_mut_slice()[i] = v; }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(self.as_mut_slice()) }
+}
+
+struct AtomicVecImpl { n: usize, a: Vec<std::sync::atomic::AtomicI64> }
+impl AtomicVecImpl {
+    fn new(n: usize) -> Self { Self { n, a: (0..n).map(|_| std::sync::atomic::AtomicI64::new(0)).collect() } }
+}
+impl ArrayImpl for AtomicVecImpl {
+    fn name(&self) -> &'static str { "rust_atomic_relaxed_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { self.a[i].store(v, std::sync::atomic::Ordering::Relaxed); }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { self.a[i].load(std::sync::atomic::Ordering::Relaxed) }
+    fn write(&mut self, i: usize, v: i64) { self.a[i].store(v, std::sync::atomic::Ordering::Relaxed); }
+    fn region(&mut self) -> Option<(*mut u8, usize)> {
+        Some((self.a.as_mut_ptr() as *mut u8, std::mem::size_of_val(&self.a[..])))
+    }
+    #[cfg(feature = "rayon")]
+    fn atomics(&self) -> Option<&[std::sync::atomic::AtomicI64]> { Some(&self.a) }
+}
+
+// One element per 64-byte cacheline (an 8x larger footprint), so no two indices ever share a line.
+struct CachePaddedVecImpl { n: usize, a: Vec<u8> }
+const CACHELINE: usize = 64;
+impl CachePaddedVecImpl {
+    fn new(n: usize) -> Self { Self { n, a: vec![0; n * CACHELINE] } }
+}
+impl ArrayImpl for CachePaddedVecImpl {
+    fn name(&self) -> &'static str { "rust_cacheline_padded_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { self.write(i, v); }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 {
+        let o = i * CACHELINE;
+        i64::from_ne_bytes(self.a[o..o + 8].try_into().unwrap())
+    }
+    fn write(&mut self, i: usize, v: i64) {
+        let o = i * CACHELINE;
+        self.a[o..o + 8].copy_from_slice(&v.to_ne_bytes());
+    }
+    fn region(&mut self) -> Option<(*mut u8, usize)> { Some((self.a.as_mut_ptr(), self.a.len())) }
+}
+
+// The slice lives in the arena's heap chunk, which stays put when the `Bump` itself is moved.
+#[cfg(feature = "slab_impl")]
+struct SlabImpl { n: usize, p: *mut i64, _bump: bumpalo::Bump }
+#[cfg(feature = "slab_impl")]
+impl SlabImpl {
+    fn new(n: usize) -> Self {
+        let bump = bumpalo::Bump::with_capacity(n * std::mem::size_of::<i64>());
+        let p = bump.alloc_slice_fill_copy(n, 0i64).as_mut_ptr();
+        Self { n, p, _bump: bump }
+    }
+    fn as_slice(&self) -> &[i64] { unsafe { std::slice::from_raw_parts(self.p, self.n) } }
+    fn as_mut_slice(&mut self) -> &mut [i64] { unsafe { std::slice::from_raw_parts_mut(self.p, self.n) } }
+}
+#[cfg(feature = "slab_impl")]
+impl ArrayImpl for SlabImpl {
+    fn name(&self) -> &'static str { "rust_slab_bumpalo_i64" }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        let a = self.as_mut_slice();
+        for x in a.iter_mut() { *x = v; }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { self.as_slice()[i] }
+    fn write(&mut self, i: usize, v: i64) { self.as_mut_slice()[i] = v; }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(self.as_mut_slice()) }
+}
+
+#[cfg(all(feature = "numa", target_os = "linux"))]
+#[link(name = "numa")]
+extern "C" {
+    fn numa_available() -> libc::c_int;
+    fn numa_alloc_interleaved(size: libc::size_t) -> *mut libc::c_void;
+    fn numa_alloc_local(size: libc::size_t) -> *mut libc::c_void;
+    fn numa_free(start: *mut libc::c_void, size: libc::size_t);
+}
+
+// libnuma allocation either striped page-by-page across all nodes or bound to the calling
+// thread's node; comparing the two exposes the cross-socket penalty on multi-node machines.
+#[cfg(all(feature = "numa", target_os = "linux"))]
+struct NumaImpl { n: usize, len: usize, p: *mut i64, interleaved: bool }
+#[cfg(all(feature = "numa", target_os = "linux"))]
+impl NumaImpl {
+    fn new(n: usize, interleaved: bool) -> Self {
+        let len = std::cmp::max(1, n) * std::mem::size_of::<i64>();
+        let p = unsafe { if interleaved { numa_alloc_interleaved(len) } else { numa_alloc_local(len) } };
+        if p.is_null() { panic!("libnuma allocation of {} bytes failed", len); }
+        Self { n, len, p: p as *mut i64, interleaved }
+    }
+}
+#[cfg(all(feature = "numa", target_os = "linux"))]
+impl Drop for NumaImpl {
+    fn drop(&mut self) { unsafe { numa_free(self.p as *mut libc::c_void, self.len); } }
+}
+#[cfg(all(feature = "numa", target_os = "linux"))]
+impl ArrayImpl for NumaImpl {
+    fn name(&self) -> &'static str { if self.interleaved { "rust_numa_interleaved_i64" } else { "rust_numa_local_i64" } }
+    fn init(&mut self, v: i64) -> i64 {
+        let t0 = Instant::now();
+        for i in 0..self.n { unsafe { *self.p.add(i) = v; } }
+        t0.elapsed().as_nanos() as i64
+    }
+    fn read(&self, i: usize) -> i64 { assert!(i < self.n); unsafe { *self.p.add(i) } }
+    fn write(&mut self, i: usize, v: i64) { assert!(i < self.n); unsafe { *self.p.add(i) = v; } }
+    fn slice_mut(&mut self) -> Option<&mut [i64]> { Some(unsafe { std::slice::from_raw_parts_mut(self.p, self.n) }) }
+}
+
+type MakeImpl = Box<dyn Fn(usize) -> Box<dyn ArrayImpl>>;
+
+// Impls left out of the default run and only benchmarked when named in --impls: the padded impl
+// needs 64 bytes per element, 6.4 GB at N = 100m and more than RAM under --Ns-auto.
+const OPT_IN_IMPLS: &[&str] = &["rust_cacheline_padded_i64"];
+
+// Factory for any `T: ArrayImpl` with `fn new(n: usize) -> Self`, paired with the name its
+// `ArrayImpl::name` returns so --impls and --list-impls never construct anything; both are checked
+// at compile time and the names by `impl_names_match`. Adding a custom impl is one more
+// `register_impl!("my_impl", MyImpl)` line in `impl_factories`. Impls whose constructor needs more
+// than n (the file-backed mmap, numa) push a closure instead.
+macro_rules! register_impl {
+    ($name:expr, $t:ty) => {{
+        let new: fn(usize) -> $t = <$t>::new;
+        ($name, Box::new(move |n| Box::new(new(n)) as Box<dyn ArrayImpl>) as MakeImpl)
+    }};
+}
+
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn impl_factories(tmpdir: &std::path::Path) -> Vec<(&'static str, MakeImpl)> {
+    #[cfg(unix)]
+    let dir = tmpdir.to_path_buf();
+    #[allow(unused_mut)]
+    let mut v: Vec<(&'static str, MakeImpl)> = vec![
+        register_impl!(<i64 as Elem>::NAME, TypedVecImpl<i64>),
+        register_impl!(<f64 as Elem>::NAME, TypedVecImpl<f64>),
+        register_impl!(<f32 as Elem>::NAME, TypedVecImpl<f32>),
+        register_impl!(<i32 as Elem>::NAME, TypedVecImpl<i32>),
+        register_impl!(<u8 as Elem>::NAME, TypedVecImpl<u8>),
+        register_impl!("rust_boxed_slice_i64", BoxedSliceImpl),
+        register_impl!("rust_hashmap_i64", HashMapImpl),
+        register_impl!("rust_btreemap_i64", BTreeMapImpl),
+        register_impl!("rust_vecdeque_i64", VecDequeImpl),
+        register_impl!("rust_aligned64_i64", AlignedVecImpl),
+        register_impl!("rust_atomic_relaxed_i64", AtomicVecImpl),
+        register_impl!("rust_growing_vec_i64", GrowingVecImpl),
+        register_impl!("rust_cacheline_padded_i64", CachePaddedVecImpl),
+        #[cfg(feature = "slab_impl")]
+        register_impl!("rust_slab_bumpalo_i64", SlabImpl),
+        #[cfg(feature = "unsafe_impl")]
+        register_impl!("rust_raw_ptr_i64", UnsafePtrImpl),
+        #[cfg(unix)]
+        register_impl!("rust_mmap_anon_i64", MmapImpl),
+        #[cfg(unix)]
+        ("rust_mmap_file_i64", Box::new(move |n| Box::new(MmapFileImpl::new(n, &dir)))),
+    ];
+    #[cfg(all(feature = "numa", target_os = "linux"))]
+    if unsafe { numa_available() } >= 0 {
+        v.push(("rust_numa_interleaved_i64", Box::new(|n| Box::new(NumaImpl::new(n, true)))));
+        v.push(("rust_numa_local_i64", Box::new(|n| Box::new(NumaImpl::new(n, false)))));
+    } else {
+        eprintln!("warning: libnuma reports NUMA unavailable; skipping the numa impls");
+    }
+    v
+}
+
+// Decimal k/m/g/t suffixes, `b` for an explicit no-op multiplier, and a trailing `p` to make the
+// unit a power of two instead (1kp = 1024, 1mp = 1048576).
+fn parse_size(tok: &str) -> Option<usize> {
+    let (p, binary) = match tok.strip_suffix(['p', 'P']) { Some(rest) => (rest, true), None => (tok, false) };
+    let (p, exp) = match p.chars().last() {
+        Some('k' | 'K') => (&p[..p.len()-1], 1),
+        Some('m' | 'M') => (&p[..p.len()-1], 2),
+        Some('g' | 'G') => (&p[..p.len()-1], 3),
+        Some('t' | 'T') => (&p[..p.len()-1], 4),
+        Some('b' | 'B') if !binary => (&p[..p.len()-1], 0),
+        _ => (p, 0),
+    };
+    if binary && exp == 0 { return None; }
+    let mult = (if binary { 1024.0_f64 } else { 1000.0 }).powi(exp);
+    let v = p.parse::<f64>().ok()?;
+    if !v.is_finite() || v < 0.0 { return None; }
+    Some((v*mult) as usize)
+}
+
+// Every power of two inside the inclusive `min..max`, both ends in parse_size syntax.
+fn parse_pow2_range(s: &str) -> Result<Vec<usize>, String> {
+    let bad = || format!("malformed --Ns-powers-of-2 {:?} (expected min..max, e.g. 1k..1g)", s);
+    let (lo, hi) = s.split_once("..").ok_or_else(bad)?;
+    let (lo, hi) = (parse_size(lo.trim()).ok_or_else(bad)?, parse_size(hi.trim()).ok_or_else(bad)?);
+    if lo > hi { return Err(format!("--Ns-powers-of-2 {:?} has min > max", s)); }
+    let mut out = Vec::new();
+    let mut p = lo.max(1).checked_next_power_of_two();
+    while let Some(v) = p.filter(|&v| v <= hi) {
+        out.push(v);
+        p = v.checked_mul(2);
+    }
+    Ok(out)
+}
+
+// Half-open `a..b` like a Rust range, capped at 1000 seeds so a typo cannot queue an hours-long run.
+fn parse_seed_range(s: &str) -> Result<Vec<u64>, String> {
+    let bad = || format!("malformed --seed-range {:?} (expected a..b)", s);
+    let (a, b) = s.split_once("..").ok_or_else(bad)?;
+    let (a, b): (u64, u64) = (a.trim().parse().map_err(|_| bad())?, b.trim().parse().map_err(|_| bad())?);
+    if a >= b { return Err(format!("--seed-range {:?} is empty (need a < b)", s)); }
+    if b - a > 1000 { return Err(format!("--seed-range {:?} spans {} seeds (max 1000)", s, b - a)); }
+    Ok((a..b).collect())
+}
+
+// Empty tokens (e.g. a trailing comma) are always ignored; malformed ones are skipped
+// unless `strict`, in which case the first one is reported.
+fn parse_sizes(s: &str, strict: bool) -> Result<Vec<usize>, String> {
     let mut out = Vec::new();
-    for mut p in s.split(',') {
+    for p in s.split(',').map(str::trim) {
         if p.is_empty() { continue }
-        let mut mult = 1.0_f64;
-        if p.ends_with('k') || p.ends_with('K') { p = &p[..p.len()-1]; mult = 1e3; }
-        if p.ends_with('m') || p.ends_with('M') { p = &p[..p.len()-1]; mult = 1e6; }
-        if p.ends_with('g') || p.ends_with('G') { p = &p[..p.len()-1]; mult = 1e9; }
-        if let Ok(v) = p.parse::<f64>() { out.push((v*mult) as usize); }
+        match parse_size(p) {
+            Some(v) => out.push(v),
+            None if strict => return Err(format!("malformed size {:?} in --Ns", p)),
+            None => {}
+        }
+    }
+    Ok(out)
+}
+
+const HEADER: &[&str] = &[
+    "timestamp_iso","impl_name","scenario","N","seed","rep_id",
+    "ops_in_run","total_time_ns","ns_per_op","init_time_ns_if_recorded",
+    "relocations_count","conversions_count",
+    "mean_total_time_ns","stddev_total_time_ns","min_total_time_ns","max_total_time_ns",
+    "p50_ns_per_op","p95_ns_per_op","p99_ns_per_op",
+    "meta_value",
+    "throughput_gbs","pct_of_peak_bandwidth",
+    "rep_count",
+    "minor_faults_delta","major_faults_delta",
+    "proc_io_rchar_delta","proc_io_wchar_delta",
+    "checksum",
+];
+
+fn set_col(row: &mut Vec<String>, col: &str, value: String) {
+    let i = HEADER.iter().position(|&h| h == col).expect("unknown column");
+    if row.len() <= i { row.resize(HEADER.len(), String::new()); }
+    row[i] = value;
+}
+
+// Output backends. JSON objects use the CSV header names as keys; numeric-looking
+// fields become JSON numbers and empty fields become null.
+enum Sink {
+    Csv(Box<Writer<std::fs::File>>),
+    Json(std::io::BufWriter<std::fs::File>, usize),
+    Jsonl(std::io::BufWriter<std::fs::File>),
+    #[cfg(feature = "sqlite")]
+    Sqlite(Box<SqliteSink>),
+    Split(Box<SplitSink>),
+}
+
+// --split-by-scenario: rows are routed on their scenario column to `<dir>/<scenario>.<format>`,
+// each file opened on first use; meta rows (keyed in that column) all go to `__meta__.<format>`.
+struct SplitSink { dir: std::path::PathBuf, format: String, append: bool, sinks: std::collections::BTreeMap<String, Sink> }
+
+// Rows go to a `benchmark_results` table, one transaction per (N, scenario) group.
+#[cfg(feature = "sqlite")]
+struct SqliteSink { conn: rusqlite::Connection, group: Option<(String, String)> }
+
+#[cfg(feature = "sqlite")]
+impl SqliteSink {
+    fn open(path: &str, append: bool) -> Result<SqliteSink, Box<dyn std::error::Error>> {
+        if !append && std::path::Path::new(path).exists() { std::fs::remove_file(path)?; }
+        let conn = rusqlite::Connection::open(path)?;
+        let cols: Vec<String> = HEADER.iter().map(|h| format!("\"{}\"", h)).collect();
+        conn.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS benchmark_results (id INTEGER PRIMARY KEY AUTOINCREMENT, {});",
+            cols.join(", ")))?;
+        Ok(SqliteSink { conn, group: None })
+    }
+
+    fn insert(&mut self, row: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        use rusqlite::types::Value;
+        let key = (row[3].clone(), row[2].clone());
+        if self.group.as_ref() != Some(&key) {
+            if self.group.is_some() { self.conn.execute_batch("COMMIT")?; }
+            self.conn.execute_batch("BEGIN")?;
+            self.group = Some(key);
+        }
+        let cols: Vec<String> = HEADER.iter().map(|h| format!("\"{}\"", h)).collect();
+        let marks = vec!["?"; HEADER.len()].join(", ");
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "INSERT INTO benchmark_results ({}) VALUES ({})", cols.join(", "), marks))?;
+        let vals = row.iter().map(|v| {
+            if v.is_empty() { Value::Null }
+            else if let Ok(i) = v.parse::<i64>() { Value::Integer(i) }
+            else if let Ok(f) = v.parse::<f64>() { Value::Real(f) }
+            else { Value::Text(v.clone()) }
+        });
+        stmt.execute(rusqlite::params_from_iter(vals))?;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.group.is_some() { self.conn.execute_batch("COMMIT")?; }
+        Ok(())
+    }
+}
+
+impl Sink {
+    // With `append`, csv/jsonl output is added to an existing file; the csv header is only
+    // written when the file is new or empty, and must otherwise match HEADER.
+    fn create(path: &str, format: &str, append: bool) -> Result<Sink, Box<dyn std::error::Error>> {
+        use std::io::Write;
+        let open_file = || std::fs::OpenOptions::new().write(true).create(true).append(append).truncate(!append).open(path);
+        let open = || open_file().map(std::io::BufWriter::new);
+        let existing = if append { std::fs::metadata(path).map(|m| m.len()).unwrap_or(0) } else { 0 };
+        Ok(match format {
+            "csv" => {
+                if existing > 0 {
+                    use std::io::BufRead;
+                    let mut first = String::new();
+                    std::io::BufReader::new(std::fs::File::open(path)?).read_line(&mut first)?;
+                    if first.trim_end() != HEADER.join(",") {
+                        return Err(format!("cannot append to {}: its header does not match this build's columns", path).into());
+                    }
+                }
+                let mut w = Writer::from_writer(open_file()?);
+                if existing == 0 { w.write_record(HEADER)?; }
+                Sink::Csv(Box::new(w))
+            }
+            "json" if append => return Err("--output-append needs --format csv or jsonl; a json array cannot be extended in place".into()),
+            "json" => {
+                let mut w = open()?;
+                w.write_all(b"[")?;
+                Sink::Json(w, 0)
+            }
+            "jsonl" => Sink::Jsonl(open()?),
+            #[cfg(feature = "sqlite")]
+            "sqlite" => Sink::Sqlite(Box::new(SqliteSink::open(path, append)?)),
+            #[cfg(not(feature = "sqlite"))]
+            "sqlite" => return Err("--format sqlite needs a build with `--features sqlite`".into()),
+            _ => return Err(format!("unknown --format {} (expected csv, json, jsonl or sqlite)", format).into()),
+        })
+    }
+
+    // Rows shorter than HEADER are padded with empty trailing columns.
+    fn split(dir: &str, format: &str, append: bool) -> Result<Sink, Box<dyn std::error::Error>> {
+        if !matches!(format, "csv" | "json" | "jsonl" | "sqlite") {
+            return Err(format!("unknown --format {} (expected csv, json, jsonl or sqlite)", format).into());
+        }
+        std::fs::create_dir_all(dir)?;
+        Ok(Sink::Split(Box::new(SplitSink { dir: dir.into(), format: format.to_string(), append, sinks: Default::default() })))
+    }
+
+    fn write_row(&mut self, row: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+        if let Sink::Split(split) = self {
+            let key = if row[1] == "__meta__" { "__meta__" } else { row[2].as_str() };
+            if !split.sinks.contains_key(key) {
+                let path = split.dir.join(format!("{}.{}", key, split.format));
+                let sink = Sink::create(&path.to_string_lossy(), &split.format, split.append)?;
+                split.sinks.insert(key.to_string(), sink);
+            }
+            return split.sinks.get_mut(key).expect("just inserted").write_row(row);
+        }
+        let mut row = row.to_vec();
+        row.resize(HEADER.len(), String::new());
+        let row = &row[..];
+        match self {
+            Sink::Csv(w) => w.write_record(row)?,
+            Sink::Json(w, count) => {
+                w.write_all(if *count == 0 { b"\n" } else { b",\n" })?;
+                serde_json::to_writer(&mut *w, &row_to_json(row))?;
+                *count += 1;
+            }
+            Sink::Jsonl(w) => {
+                serde_json::to_writer(&mut *w, &row_to_json(row))?;
+                w.write_all(b"\n")?;
+            }
+            #[cfg(feature = "sqlite")]
+            Sink::Sqlite(db) => db.insert(row)?,
+            Sink::Split(_) => unreachable!("routed above"),
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+        match self {
+            Sink::Csv(w) => w.flush()?,
+            Sink::Json(w, _) | Sink::Jsonl(w) => w.flush()?,
+            // committed per (N, scenario) group instead
+            #[cfg(feature = "sqlite")]
+            Sink::Sqlite(_) => {}
+            Sink::Split(split) => for sink in split.sinks.values_mut() { sink.flush()? },
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), Box<dyn std::error::Error>> {
+        use std::io::Write;
+        match self {
+            Sink::Csv(mut w) => w.flush()?,
+            Sink::Json(mut w, _) => { w.write_all(b"\n]\n")?; w.flush()?; }
+            Sink::Jsonl(mut w) => w.flush()?,
+            #[cfg(feature = "sqlite")]
+            Sink::Sqlite(db) => db.finish()?,
+            Sink::Split(split) => for sink in split.sinks.into_values() { sink.finish()? },
+        }
+        Ok(())
+    }
+}
+
+fn row_to_json(row: &[String]) -> serde_json::Value {
+    let obj = HEADER.iter().zip(row).map(|(&k, v)| {
+        let val = if v.is_empty() {
+            serde_json::Value::Null
+        } else if let Ok(i) = v.parse::<i64>() {
+            i.into()
+        } else if let Some(f) = v.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+            serde_json::Value::Number(f)
+        } else {
+            v.clone().into()
+        };
+        (k.to_string(), val)
+    }).collect();
+    serde_json::Value::Object(obj)
+}
+
+// (mean, population stddev, min, max)
+fn summarize(xs: &[i64]) -> (f64, f64, i64, i64) {
+    let mean = xs.iter().map(|&x| x as f64).sum::<f64>() / xs.len() as f64;
+    let var = xs.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / xs.len() as f64;
+    (mean, var.sqrt(), *xs.iter().min().unwrap(), *xs.iter().max().unwrap())
+}
+
+// stddev / mean (population), 0 for a zero mean
+fn coeff_of_variation(xs: &[f64]) -> f64 {
+    let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+    if mean == 0.0 { return 0.0; }
+    let var = xs.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / xs.len() as f64;
+    var.sqrt() / mean.abs()
+}
+
+// nearest-rank percentile over an ascending slice
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+type RunKey = (String, String, usize, u64);
+
+// total_time_ns / ops_in_run: unlike the ns_per_op column it is a time per op in every scenario
+// (INIT_* report 0 there, INIT_FILL_COPY_FROM_SLICE GB/s); None for empty or timed-out runs
+fn time_per_op(total_ns: i64, ops: usize) -> Option<f64> {
+    (ops > 0 && total_ns >= 0).then(|| total_ns as f64 / ops as f64)
+}
+
+// Mean time_per_op per (impl_name, scenario, N, seed) over the per-rep rows of a CSV written by
+// this tool; summary, meta and timed-out sentinel rows are ignored.
+fn load_baseline(path: &str) -> Result<std::collections::HashMap<RunKey, f64>, Box<dyn std::error::Error>> {
+    let mut rdr = csv::Reader::from_path(path)?;
+    let hdr = rdr.headers()?.clone();
+    let col = |name: &str| hdr.iter().position(|h| h == name).ok_or_else(|| format!("{}: no {} column", path, name));
+    let (ci, cs, cn, cseed, crep) = (col("impl_name")?, col("scenario")?, col("N")?, col("seed")?, col("rep_id")?);
+    let (cops, ctot) = (col("ops_in_run")?, col("total_time_ns")?);
+    let mut sums: std::collections::HashMap<RunKey, (f64, usize)> = std::collections::HashMap::new();
+    for rec in rdr.records() {
+        let rec = rec?;
+        if rec.get(crep).and_then(|r| r.parse::<usize>().ok()).is_none() { continue; }
+        let (Some(n), Some(seed), Some(ops), Some(tot)) = (rec[cn].parse().ok(), rec[cseed].parse().ok(), rec[cops].parse().ok(), rec[ctot].parse().ok()) else { continue };
+        let Some(v) = time_per_op(tot, ops) else { continue };
+        let e = sums.entry((rec[ci].to_string(), rec[cs].to_string(), n, seed)).or_insert((0.0, 0));
+        e.0 += v;
+        e.1 += 1;
+    }
+    Ok(sums.into_iter().map(|(k, (sum, c))| (k, sum / c as f64)).collect())
+}
+
+// Prints one line per run present in both sets and returns (any slowed down by more than
+// threshold, runs compared); a zero baseline time gives no usable ratio and is skipped.
+fn compare_report(baseline: &std::collections::HashMap<RunKey, f64>, current: &[(RunKey, f64)], threshold: f64) -> (bool, usize) {
+    let mut regressed = false;
+    let mut matched = 0;
+    println!("{:<28} {:<26} {:>12} {:>8} {:>14} {:>14} {:>8}  result", "impl", "scenario", "N", "seed", "baseline_ns", "current_ns", "ratio");
+    for (key, cur) in current {
+        let Some(&base) = baseline.get(key) else { continue };
+        let ratio = cur / base;
+        if !ratio.is_finite() { continue; }
+        matched += 1;
+        let fail = ratio > 1.0 + threshold;
+        regressed |= fail;
+        println!("{:<28} {:<26} {:>12} {:>8} {:>14.4} {:>14.4} {:>8.3}  {}",
+                 key.0, key.1, key.2, key.3, base, cur, ratio, if fail { "FAIL" } else { "PASS" });
+    }
+    (regressed, matched)
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let out = std::process::Command::new(cmd).args(args).output().ok()?;
+    if !out.status.success() { return None; }
+    String::from_utf8(out.stdout).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+// first "key: value" line of a /proc style file whose key matches
+fn proc_field(path: &str, key: &str) -> Option<String> {
+    std::fs::read_to_string(path).ok()?.lines()
+        .find(|l| l.split(':').next().map(str::trim) == Some(key))
+        .and_then(|l| l.split_once(':')).map(|(_, v)| v.trim().to_string())
+}
+
+// Smallest gap between consecutive `Instant::now()` calls over 1000 reads: the timer's effective
+// resolution plus its call cost, i.e. the floor under any single timed region.
+fn clock_overhead_ns() -> u64 {
+    let mut prev = Instant::now();
+    let mut best = u64::MAX;
+    for _ in 0..1000 {
+        let t = Instant::now();
+        best = best.min(t.duration_since(prev).as_nanos() as u64);
+        prev = t;
+    }
+    best
+}
+
+// (minor, major) page faults so far; /proc/self/status carries no fault counters, so read
+// minflt/majflt (fields 10 and 12) from /proc/self/stat, skipping past the parenthesised comm
+#[cfg(target_os = "linux")]
+fn fault_counts() -> Option<(u64, u64)> {
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    let f: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+    Some((f.get(7)?.parse().ok()?, f.get(9)?.parse().ok()?))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn fault_counts() -> Option<(u64, u64)> { None }
+
+// (rchar, wchar) bytes moved through read/write-style syscalls, from /proc/self/io; one read per
+// sample, since each read of the file is itself counted in rchar
+#[cfg(target_os = "linux")]
+fn io_counts() -> Option<(u64, u64)> {
+    let io = std::fs::read_to_string("/proc/self/io").ok()?;
+    let field = |key: &str| io.lines().find_map(|l| l.strip_prefix(key)?.strip_prefix(':')?.trim().parse().ok());
+    Some((field("rchar")?, field("wchar")?))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn io_counts() -> Option<(u64, u64)> { None }
+
+fn host_name() -> Option<String> {
+    read_trimmed("/proc/sys/kernel/hostname")
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .or_else(|| command_output("hostname", &[]))
+}
+
+fn os_version() -> Option<String> {
+    let version = match env::consts::OS {
+        "linux" => std::fs::read_to_string("/etc/os-release").ok()
+            .and_then(|s| s.lines().find_map(|l| l.strip_prefix("PRETTY_NAME=")).map(|v| v.trim_matches('"').to_string()))
+            .or_else(|| command_output("uname", &["-r"])),
+        "macos" => command_output("sw_vers", &["-productVersion"]),
+        _ => None,
+    }?;
+    Some(format!("{} {}", env::consts::OS, version))
+}
+
+fn cpu_model() -> Option<String> {
+    match env::consts::OS {
+        "linux" => proc_field("/proc/cpuinfo", "model name"),
+        "macos" => command_output("sysctl", &["-n", "machdep.cpu.brand_string"]),
+        _ => None,
+    }
+}
+
+fn total_ram_bytes() -> Option<u64> {
+    match env::consts::OS {
+        "linux" => proc_field("/proc/meminfo", "MemTotal")?.split_whitespace().next()?.parse::<u64>().ok().map(|kb| kb * 1024),
+        "macos" => command_output("sysctl", &["-n", "hw.memsize"])?.parse().ok(),
+        _ => None,
+    }
+}
+
+// (L1d, L2, L3) sizes in bytes, each None when the platform does not report it
+fn cache_sizes() -> [Option<u64>; 3] {
+    #[cfg(target_os = "linux")]
+    {
+        let q = |name| { let v = unsafe { libc::sysconf(name) }; if v > 0 { Some(v as u64) } else { None } };
+        [q(libc::_SC_LEVEL1_DCACHE_SIZE), q(libc::_SC_LEVEL2_CACHE_SIZE), q(libc::_SC_LEVEL3_CACHE_SIZE)]
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let q = |key| command_output("sysctl", &["-n", key]).and_then(|v| v.parse::<u64>().ok()).filter(|&v| v > 0);
+        [q("hw.l1dcachesize"), q("hw.l2cachesize"), q("hw.l3cachesize")]
+    }
+}
+
+// i64 element counts spanning the memory hierarchy: L1/16, L1/2, L1, L2/2, L2, L3/2, L3, RAM/4
+fn auto_sizes() -> Vec<usize> {
+    let [l1, l2, l3] = cache_sizes();
+    let mut bytes: Vec<u64> = Vec::new();
+    if let Some(l1) = l1 { bytes.extend([l1 / 16, l1 / 2, l1]); }
+    if let Some(l2) = l2 { bytes.extend([l2 / 2, l2]); }
+    if let Some(l3) = l3 { bytes.extend([l3 / 2, l3]); }
+    if let Some(ram) = total_ram_bytes() { bytes.push(ram / 4); }
+    let mut out: Vec<usize> = bytes.into_iter().map(|b| (b / std::mem::size_of::<i64>() as u64) as usize).filter(|&n| n > 0).collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+// (key, value) metadata pairs; anything that cannot be read is reported as "unknown"
+fn machine_metadata() -> Vec<(&'static str, String)> {
+    let unknown = || "unknown".to_string();
+    let built = env!("BUILD_TIMESTAMP").parse::<i64>().ok()
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0)).map(|t| t.to_rfc3339());
+    vec![
+        ("hostname", host_name().unwrap_or_else(unknown)),
+        ("os", os_version().unwrap_or_else(|| env::consts::OS.to_string())),
+        ("cpu_model", cpu_model().unwrap_or_else(unknown)),
+        ("total_ram_bytes", total_ram_bytes().map(|b| b.to_string()).unwrap_or_else(unknown)),
+        ("rustc_version", env!("RUSTC_VERSION").to_string()),
+        ("build_timestamp", built.unwrap_or_else(unknown)),
+        ("build_profile", env!("BUILD_PROFILE").to_string()),
+        ("git_commit_hash", env!("GIT_COMMIT_HASH").to_string()),
+        ("git_dirty", env!("GIT_DIRTY").to_string()),
+    ]
+}
+
+fn meta_row(key: &str, value: &str) -> Vec<String> {
+    let mut row = vec![now_iso(), "__meta__".to_string(), key.to_string()];
+    set_col(&mut row, "meta_value", value.to_string());
+    row
+}
+
+// bytes_per_op comes from the scenario; None (ill-defined) reports -1
+fn throughput_gbs(bytes_per_op: Option<f64>, ops: usize, total_ns: i64) -> f64 {
+    match bytes_per_op {
+        Some(b) => (ops as f64 * b) / (total_ns as f64 * 1e-9) / 1e9,
+        None => -1.0,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(core: usize) -> std::io::Result<()> {
+    if core >= libc::CPU_SETSIZE as usize {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "core id out of range"));
+    }
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_cpu(_core: usize) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "CPU pinning is only implemented on Linux"))
+}
+
+// Keeps an impl's pages resident until dropped; declare it after the impl so it unlocks first.
+#[cfg(unix)]
+struct MemLock { p: *mut u8, len: usize }
+
+#[cfg(unix)]
+impl MemLock {
+    fn new(arr: &mut dyn ArrayImpl) -> std::io::Result<Option<MemLock>> {
+        let Some((p, len)) = arr.region() else { return Ok(None) };
+        if len == 0 { return Ok(None); }
+        if unsafe { libc::mlock(p as *const libc::c_void, len) } != 0 { return Err(std::io::Error::last_os_error()); }
+        Ok(Some(MemLock { p, len }))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for MemLock {
+    fn drop(&mut self) { unsafe { libc::munlock(self.p as *const libc::c_void, self.len); } }
+}
+
+// Asks for transparent huge pages over the page-aligned interior of an impl's backing memory.
+#[cfg(target_os = "linux")]
+fn advise_hugepages(arr: &mut dyn ArrayImpl) -> std::io::Result<()> {
+    let Some((p, len)) = arr.region() else { return Ok(()) };
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let start = (p as usize).next_multiple_of(page);
+    let end = (p as usize + len) / page * page;
+    if end <= start { return Ok(()); }
+    if unsafe { libc::madvise(start as *mut libc::c_void, end - start, libc::MADV_HUGEPAGE) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn advise_hugepages(_arr: &mut dyn ArrayImpl) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "MADV_HUGEPAGE is only available on Linux"))
+}
+
+// Total AnonHugePages across every mapping of this process, in kB.
+fn anon_huge_kb() -> Option<u64> {
+    let smaps = std::fs::read_to_string("/proc/self/smaps").ok()?;
+    Some(smaps.lines().filter_map(|l| l.strip_prefix("AnonHugePages:"))
+        .filter_map(|v| v.trim().trim_end_matches("kB").trim().parse::<u64>().ok()).sum())
+}
+
+// Every flag main understands and whether it takes a value; BENCH_<FLAG> env vars are looked up
+// from this list, so new flags belong here as well as in the match.
+const FLAGS: &[(&str, bool)] = &[
+    ("--Ns", true), ("--Ns-auto", false), ("--Ns-powers-of-2", true), ("--reps", true),
+    ("--reps-adaptive", false), ("--cv-threshold", true), ("--reps-max", true),
+    ("--cooldown-ms", true), ("--min-runtime-ms", true), ("--warmup", true), ("--timeout-ms", true),
+    ("--seed", true), ("--seeds", true), ("--seed-range", true), ("--outfile", true),
+    ("--outfile-template", true), ("--no-summary", false), ("--progress", false),
+    ("--emit-intermediate", false), ("--verify", false), ("--checksum", false),
+    ("--compare-csv", true), ("--regression-threshold", true), ("--meta", false),
+    ("--peak-bw-gbs", true), ("--output-append", false), ("--split-by-scenario", false),
+    ("--outdir", true), ("--strict", false), ("--format", true), ("--scenarios", true),
+    ("--scenarios-file", true), ("--impls", true), ("--list-scenarios", false),
+    ("--list-impls", false), ("--dry-run", false), ("--sort-queries", true),
+    ("--zigzag-passes", true), ("--threads", true), ("--pin-cpu", true), ("--mlocked", false),
+    ("--prefetch", false), ("--no-black-box", false), ("--repeat-inner", true),
+    ("--clock-overhead", false), ("--hugepages", false), ("--tmpdir", true), ("--config", true),
+];
+
+// `--reps` is read from BENCH_REPS, `--no-summary` from BENCH_NO_SUMMARY, and so on. A switch is
+// on unless its variable is empty, `0` or `false`.
+fn env_args() -> Vec<String> {
+    let mut out = Vec::new();
+    for &(flag, takes_value) in FLAGS {
+        let var = format!("BENCH_{}", flag.trim_start_matches('-').replace('-', "_").to_uppercase());
+        let Ok(v) = env::var(&var) else { continue };
+        if takes_value { out.extend([flag.to_string(), v]); }
+        else if !matches!(v.trim(), "" | "0" | "false") { out.push(flag.to_string()); }
     }
     out
 }
 
-fn rand_val(rng: &mut StdRng) -> i64 { (rng.gen_range(0..2001) as i64) - 1000 }
-
-fn mk_idx(rng: &mut StdRng, m: usize, n: usize) -> Vec<usize> {
-    (0..m).map(|_| rng.gen_range(0..n)).collect()
-}
-
-fn run_scenario(arr: &mut dyn ArrayImpl, scenario: &str, n: usize, seed: u64) -> (usize, i64, f64, i64) {
-    let mut rng = StdRng::seed_from_u64(seed);
-
-    match scenario {
-        "INIT_ONLY" => {
-            let t0 = Instant::now();
-            arr.init(42);
-            let el = t0.elapsed().as_nanos() as i64;
-            (1, el, 0.0, el)
-        }
-        "READ_UNWRITTEN" => {
-            arr.init(123);
-            let m = std::cmp::min(1_000_000usize, 10*n);
-            let idx = mk_idx(&mut rng, m, n);
-            let t0 = Instant::now();
-            let mut s: i64 = 0;
-            for &j in &idx { s = s.wrapping_add(arr.read(black_box(j))); }
-            let el = t0.elapsed().as_nanos() as i64;
-            black_box(s);
-            (m, el, el as f64 / m as f64, 0)
-        }
-        "WRITE_SEQUENTIAL" => {
-            arr.init(0);
-            let t0 = Instant::now();
-            for i in 0..n { arr.write(i, i as i64); }
-            let el = t0.elapsed().as_nanos() as i64;
-            (n, el, el as f64 / n as f64, 0)
-        }
-        "WRITE_RANDOM" => {
-            arr.init(0);
-            let m = std::cmp::min(1_000_000usize, n);
-            let idx = mk_idx(&mut rng, m, n);
-            let t0 = Instant::now();
-            for &j in &idx { arr.write(black_box(j), rand_val(&mut rng)); }
-            let el = t0.elapsed().as_nanos() as i64;
-            (m, el, el as f64 / m as f64, 0)
-        }
-        s if s.starts_with("MIXED_") => {
-            let p = &s[6..];
-            let rpos = p.find('R').unwrap();
-            let wpos = p.find('W').unwrap();
-            let read_pct: i32 = p[(rpos+1)..wpos].parse().unwrap();
-
-            arr.init(42);
-            let m = std::cmp::min(1_000_000usize, n);
-            let idx = mk_idx(&mut rng, m, n);
-            let ops: Vec<u8> = (0..m).map(|_| if rng.gen_range(0..100) < read_pct {0} else {1}).collect();
-
-            let t0 = Instant::now();
-            let mut ssum: i64 = 0;
-            for t in 0..m {
-                if ops[t] == 0 { ssum = ssum.wrapping_add(arr.read(black_box(idx[t]))); }
-                else { arr.write(black_box(idx[t]), rand_val(&mut rng)); }
+// Scenario names for --scenarios-file, one per line; blank lines and `#` comment lines are skipped.
+fn scenarios_file(path: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let names: Vec<String> = std::fs::read_to_string(path)?.lines().map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(String::from)
+        .collect();
+    if names.is_empty() { return Err(format!("--scenarios-file {}: no scenario names", path).into()); }
+    Ok(names)
+}
+
+// Flattens a TOML file whose keys are flag names (`Ns = ["1k", "100k"]`, `reps = 5`,
+// `no-summary = true`) into the equivalent argument list; arrays join with commas.
+fn config_args(path: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    fn scalar(v: &toml::Value) -> Option<String> {
+        match v {
+            toml::Value::String(s) => Some(s.clone()),
+            toml::Value::Integer(i) => Some(i.to_string()),
+            toml::Value::Float(f) => Some(f.to_string()),
+            _ => None,
+        }
+    }
+    let table: toml::Table = std::fs::read_to_string(path)?.parse()?;
+    let mut out = Vec::new();
+    for (key, v) in &table {
+        let flag = format!("--{}", key);
+        // a typo would otherwise be dropped, and a switch value on a valued flag would take the next argument
+        let Some(&(_, takes_value)) = FLAGS.iter().find(|(f, _)| *f == flag) else {
+            return Err(format!("{}: unknown key {} (not a flag)", path, key).into());
+        };
+        match (v, takes_value) {
+            (toml::Value::Boolean(_), true) => return Err(format!("{}: {} takes a value, not true/false", path, key).into()),
+            (toml::Value::Boolean(true), false) => out.push(flag),
+            (toml::Value::Boolean(false), false) => {}
+            (_, false) => return Err(format!("{}: {} is a switch; use true or false", path, key).into()),
+            (toml::Value::Array(items), true) => {
+                let parts: Option<Vec<String>> = items.iter().map(scalar).collect();
+                let parts = parts.ok_or_else(|| format!("{}: {} must be an array of strings or numbers", path, key))?;
+                out.extend([flag, parts.join(",")]);
             }
-            let el = t0.elapsed().as_nanos() as i64;
-            black_box(ssum);
-            (m, el, el as f64 / m as f64, 0)
-        }
-        "ADVERSARIAL_HOTSPOT" => {
-            arr.init(0);
-            let m = std::cmp::min(1_000_000usize, n);
-            let hot = std::cmp::max(1usize, n/10);
-            let t0 = Instant::now();
-            for _ in 0..m {
-                let j = if rng.gen_range(0..2) == 0 { rng.gen_range(0..hot) } else { rng.gen_range(0..n) };
-                arr.write(black_box(j), rand_val(&mut rng));
+            (other, true) => {
+                let val = scalar(other).ok_or_else(|| format!("{}: unsupported value for {}", path, key))?;
+                out.extend([flag, val]);
             }
-            let el = t0.elapsed().as_nanos() as i64;
-            (m, el, el as f64 / m as f64, 0)
         }
-        _ => panic!("unknown scenario"),
     }
+    Ok(out)
 }
 
+fn plural(k: usize, one: &str, many: &str) -> String { format!("{} {}", k, if k == 1 { one } else { many }) }
+
+fn hms(secs: u64) -> String { format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60) }
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut Ns = String::from("10000,100000,1000000");
+    let started = Utc::now();
+    // empty means the 10k/100k/1m default unless --Ns-powers-of-2 supplies sizes
+    let mut ns_arg = String::new();
+    let mut ns_pow2 = String::new();
     let mut reps: usize = 3;
+    let mut warmup: usize = 0;
+    let mut timeout_ms: u128 = 0;
     let mut seed: u64 = 42;
+    let mut seed_list: Vec<u64> = Vec::new();
     let mut outfile = String::from("rust-results.csv");
+    let mut outfile_template: Option<String> = None;
+    let mut tmpdir = env::temp_dir();
+    let mut scenario_filter = String::new();
+    let mut scenario_file: Option<String> = None;
+    let mut impl_filter = String::new();
+    let mut list_scenarios = false;
+    let mut list_impls = false;
+    let mut dry_run = false;
+    let mut format = String::from("csv");
+    let mut output_append = false;
+    let mut split_by_scenario = false;
+    let mut outdir: Option<String> = None;
+    let mut strict = false;
+    let mut ns_auto = false;
+    let mut reps_adaptive = false;
+    let mut cv_threshold: f64 = 0.02;
+    let mut reps_max: usize = 20;
+    let mut cooldown_ms: u64 = 0;
+    let mut min_runtime_ms: u64 = 0;
+    let mut scenario_opts = ScenarioOpts::default();
+    let mut threads: Option<usize> = None;
+    let mut pin_cpu: Option<usize> = None;
+    let mut mlocked = false;
+    let mut hugepages = false;
+    let mut summary = true;
+    let mut compare_csv: Option<String> = None;
+    let mut progress = false;
+    let mut emit_intermediate = false;
+    let mut verify = false;
+    let mut checksum = false;
+    let mut clock_overhead = false;
+    let mut regression_threshold = 0.05_f64;
+    let mut meta = false;
+    let mut peak_bw_gbs: Option<f64> = None;
 
-    let mut args = env::args().skip(1);
+    // env and config-file values become leading flags, so later sources override earlier ones:
+    // BENCH_* env vars, then the --config file, then the command line
+    let from_env = env_args();
+    let cli: Vec<String> = env::args().skip(1).collect();
+    let config_path = [&cli, &from_env].into_iter()
+        .find_map(|v| v.iter().position(|a| a == "--config").and_then(|i| v.get(i + 1)));
+    let from_config = match config_path { Some(path) => config_args(path)?, None => Vec::new() };
+    let mut args = from_env.into_iter().chain(from_config).chain(cli);
     while let Some(a) = args.next() {
         match a.as_str() {
-            "--Ns" => if let Some(v) = args.next() { Ns = v },
+            "--Ns" => if let Some(v) = args.next() { ns_arg = v },
+            "--Ns-auto" => ns_auto = true,
+            "--Ns-powers-of-2" => if let Some(v) = args.next() { ns_pow2 = v },
             "--reps" => if let Some(v) = args.next() { reps = v.parse().unwrap_or(3) },
+            "--reps-adaptive" => reps_adaptive = true,
+            "--cv-threshold" => if let Some(v) = args.next() { cv_threshold = v.parse().unwrap_or(0.02) },
+            "--reps-max" => if let Some(v) = args.next() { reps_max = v.parse().unwrap_or(20) },
+            "--cooldown-ms" => if let Some(v) = args.next() { cooldown_ms = v.parse().unwrap_or(0) },
+            "--min-runtime-ms" => if let Some(v) = args.next() { min_runtime_ms = v.parse().unwrap_or(0) },
+            "--warmup" => if let Some(v) = args.next() { warmup = v.parse().unwrap_or(0) },
+            "--timeout-ms" => if let Some(v) = args.next() { timeout_ms = v.parse().unwrap_or(0) },
             "--seed" => if let Some(v) = args.next() { seed = v.parse().unwrap_or(42) },
+            "--seeds" => if let Some(v) = args.next() {
+                seed_list = v.split(',').filter_map(|p| p.trim().parse().ok()).collect();
+            },
+            "--seed-range" => if let Some(v) = args.next() { seed_list = parse_seed_range(&v)? },
             "--outfile" => if let Some(v) = args.next() { outfile = v },
+            "--outfile-template" => outfile_template = args.next(),
+            "--no-summary" => summary = false,
+            "--progress" => progress = true,
+            "--emit-intermediate" => emit_intermediate = true,
+            "--verify" => verify = true,
+            "--checksum" => checksum = true,
+            "--clock-overhead" => clock_overhead = true,
+            "--compare-csv" => compare_csv = args.next(),
+            "--regression-threshold" => if let Some(v) = args.next() { regression_threshold = v.parse().unwrap_or(regression_threshold) },
+            "--meta" => meta = true,
+            "--peak-bw-gbs" => if let Some(v) = args.next() { peak_bw_gbs = v.parse().ok().filter(|&p: &f64| p > 0.0) },
+            "--output-append" => output_append = true,
+            "--split-by-scenario" => split_by_scenario = true,
+            "--outdir" => outdir = args.next(),
+            "--strict" => strict = true,
+            "--format" => if let Some(v) = args.next() { format = v },
+            "--scenarios" => if let Some(v) = args.next() { scenario_filter = v },
+            "--scenarios-file" => scenario_file = args.next(),
+            "--impls" => if let Some(v) = args.next() { impl_filter = v },
+            "--list-scenarios" => list_scenarios = true,
+            "--list-impls" => list_impls = true,
+            "--dry-run" => dry_run = true,
+            "--sort-queries" => if let Some(v) = args.next() { scenario_opts.sort_queries = v.parse().ok() },
+            "--zigzag-passes" => if let Some(v) = args.next() { scenario_opts.zigzag_passes = v.parse().ok() },
+            "--threads" => if let Some(v) = args.next() { threads = v.parse().ok().filter(|&t: &usize| t > 0) },
+            "--pin-cpu" => if let Some(v) = args.next() { pin_cpu = v.parse().ok() },
+            "--mlocked" => mlocked = true,
+            "--prefetch" => scenario_opts.prefetch = true,
+            "--repeat-inner" => if let Some(v) = args.next() { scenario_opts.repeat_inner = v.parse().unwrap_or(1) },
+            "--no-black-box" => scenario_opts.no_black_box = true,
+            "--hugepages" => hugepages = true,
+            "--tmpdir" => if let Some(v) = args.next() { tmpdir = v.into() },
+            "--config" => { args.next(); },
             _ => {},
         }
     }
 
-    let mut wtr = Writer::from_path(outfile.clone())?;
-    wtr.write_record(&[
-        "timestamp_iso","impl_name","scenario","N","seed","rep_id",
-        "ops_in_run","total_time_ns","ns_per_op","init_time_ns_if_recorded",
-        "relocations_count","conversions_count",
-    ])?;
+    if env!("BUILD_PROFILE") == "debug" {
+        eprintln!("warning: this binary was built without optimisations; timings are not representative (use --release)");
+    }
+
+    if let Some(t) = threads {
+        #[cfg(feature = "rayon")]
+        rayon::ThreadPoolBuilder::new().num_threads(t).build_global()?;
+        #[cfg(not(feature = "rayon"))]
+        eprintln!("warning: --threads {} ignored; parallel scenarios need `--features rayon`", t);
+    }
+
+    if list_scenarios {
+        for s in scenarios::all_scenarios(&scenario_opts) { println!("{}", s.name()); }
+        return Ok(());
+    }
+    if list_impls {
+        for (name, _) in impl_factories(&tmpdir) { println!("{}", name); }
+        return Ok(());
+    }
+
 
     let n_list = {
-        let v = parse_sizes(&Ns);
+        let mut v = if ns_auto { auto_sizes() } else { parse_sizes(&ns_arg, strict)? };
+        if !ns_pow2.is_empty() { v.extend(parse_pow2_range(&ns_pow2)?); }
         if v.is_empty() { vec![10_000usize, 100_000, 1_000_000] } else { v }
     };
-    let seeds = vec![seed];
-    let scenarios = vec![
-        "INIT_ONLY","READ_UNWRITTEN","WRITE_SEQUENTIAL","WRITE_RANDOM",
-        "MIXED_R90W10","MIXED_R80W20","MIXED_R70W30","MIXED_R50W50","MIXED_R30W70","MIXED_R10W90",
-        "ADVERSARIAL_HOTSPOT",
-    ];
+    let seeds = if seed_list.is_empty() { vec![seed] } else { seed_list };
+    if let Some(path) = &scenario_file {
+        // joins any --scenarios list, so the two can be combined
+        for name in scenarios_file(path)? { scenario_filter = format!("{},{}", scenario_filter, name); }
+    }
+    let scenarios = select_scenarios(&scenario_filter, &scenario_opts)?;
+
+    let wanted: Vec<&str> = impl_filter.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+    let (names, impls): (Vec<&str>, Vec<MakeImpl>) = impl_factories(&tmpdir).into_iter()
+        .filter(|(name, _)| if wanted.is_empty() { !OPT_IN_IMPLS.contains(name) } else { wanted.contains(name) })
+        .unzip();
+    // same as --scenarios: a misspelt or not-compiled-in name is an error, not an empty run
+    if let Some(p) = wanted.iter().find(|p| !names.contains(p)) {
+        return Err(format!("unknown impl {:?} in --impls (see --list-impls)", p).into());
+    }
+
+    // both stop a run somewhere between --reps and --reps-max, so the plan is an upper bound
+    let reps_vary = reps_adaptive || min_runtime_ms > 0;
+    let plan_reps = if reps_vary { std::cmp::max(reps, reps_max) } else { reps };
+    let total = n_list.len() * scenarios.len() * impls.len() * seeds.len() * plan_reps;
+    if dry_run {
+        println!("would run {}{} benchmarks across {}, {}, {}, {}, {}",
+                 if reps_vary { "up to " } else { "" }, total,
+                 plural(n_list.len(), "N", "Ns"), plural(scenarios.len(), "scenario", "scenarios"),
+                 plural(impls.len(), "impl", "impls"), plural(seeds.len(), "seed", "seeds"),
+                 plural(plan_reps, "rep", "reps"));
+        return Ok(());
+    }
+
+    if let Some(core) = pin_cpu {
+        if let Err(e) = pin_to_cpu(core) { eprintln!("warning: could not pin to CPU {}: {}; continuing unpinned", core, e); }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    if scenario_opts.prefetch { eprintln!("warning: --prefetch is only implemented on x86_64; the hints are no-ops here"); }
+    #[cfg(not(unix))]
+    if mlocked { eprintln!("warning: --mlocked is only supported on unix; continuing without locking"); }
+    #[cfg(unix)]
+    let mut mlock_warned = false;
+    let mut hugepages_warned = false;
+    let mut uncovered_warned = std::collections::HashSet::new();
+    let mut hugepages_kb: Option<u64> = None;
+
+    if let Some(t) = &outfile_template { outfile = expand_outfile_template(t, started); }
+    // load before the run so a bad baseline path fails fast, and before a same-path outfile is truncated
+    let baseline = match &compare_csv { Some(p) => load_baseline(p)?, None => Default::default() };
+    let mut wtr = if split_by_scenario {
+        let dir = outdir.as_deref().ok_or("--split-by-scenario needs --outdir <dir>")?;
+        outfile = dir.to_string();
+        Sink::split(dir, &format, output_append)?
+    } else {
+        Sink::create(&outfile, &format, output_append)?
+    };
+    let mut summaries: Vec<Vec<String>> = Vec::new();
+    let mut current_means: Vec<(RunKey, f64)> = Vec::new();
+    let mut verify_failed = false;
+    let progress_tty = std::io::IsTerminal::is_terminal(&std::io::stderr());
+    let mut run_no = 0usize;
+    let bench_start = Instant::now();
+    if meta {
+        for (k, v) in machine_metadata() { wtr.write_row(&meta_row(k, &v))?; }
+        wtr.write_row(&meta_row("cooldown_ms", &cooldown_ms.to_string()))?;
+        wtr.write_row(&meta_row("black_box", if scenario_opts.no_black_box { "off" } else { "on" }))?;
+    }
+    let clock_ns = if clock_overhead { Some(clock_overhead_ns()) } else { None };
+    if let Some(c) = clock_ns { wtr.write_row(&meta_row("clock_overhead_ns", &c.to_string()))?; }
 
     for &n in &n_list {
         for s in &scenarios {
-            for &seed in &seeds {
-                for rep in 1..=reps {
-                    let mut arr = VecImpl::new(n);
-                    let (ops, tot, nspop, initns) = run_scenario(&mut arr, s, n, seed);
-                    wtr.write_record(&[
-                        now_iso(), arr.name().to_string(), s.to_string(),
-                        format!("{}", n), format!("{}", seed), format!("{}", rep),
-                        format!("{}", ops), format!("{}", tot), format!("{:.4}", nspop),
-                        format!("{}", initns), "0".to_string(), "0".to_string()
-                    ])?;
+            for make in &impls {
+                for &seed in &seeds {
+                    for _ in 0..warmup {
+                        let mut arr = make(n);
+                        std::hint::black_box(s.run(arr.as_mut(), n, seed));
+                    }
+                    let mut last_wall_ms: u128 = 0;
+                    let mut samples: Vec<(usize, i64, f64)> = Vec::new();
+                    let mut first_checksum: Option<i64> = None;
+                    let mut name = "";
+                    // adaptive mode and --min-runtime-ms treat --reps as the minimum and stop once
+                    // ns_per_op settles and the summed total_time_ns reaches the budget, respectively
+                    let max_reps = plan_reps;
+                    for rep in 1..=max_reps {
+                        if timeout_ms > 0 && last_wall_ms > timeout_ms {
+                            // previous rep blew the budget: record a sentinel row and drop the rest, before
+                            // allocating another array; `name` is the impl that ran that rep
+                            wtr.write_row(&[
+                                now_iso(), name.to_string(), s.name().to_string(),
+                                format!("{}", n), format!("{}", seed), format!("{}", rep),
+                                "0".to_string(), "-1".to_string(), String::new(),
+                                String::new(), "0".to_string(), "0".to_string()
+                            ])?;
+                            wtr.flush()?;
+                            break;
+                        }
+                        if samples.len() >= if reps_adaptive { std::cmp::max(2, reps) } else { reps } {
+                            let v: Vec<f64> = samples.iter().map(|x| x.2).collect();
+                            let settled = !reps_adaptive || coeff_of_variation(&v) < cv_threshold;
+                            let measured_ns: i64 = samples.iter().map(|x| x.1).sum();
+                            if settled && measured_ns as u64 >= min_runtime_ms * 1_000_000 { break; }
+                        }
+                        if cooldown_ms > 0 && rep > 1 { std::thread::sleep(std::time::Duration::from_millis(cooldown_ms)); }
+                        // elapsed per completed rep (warmups and cooldowns included) times the reps left
+                        let eta = (run_no > 0).then(|| {
+                            let per_run = bench_start.elapsed().as_secs_f64() / run_no as f64;
+                            format!("ETA: {}", hms((per_run * total.saturating_sub(run_no) as f64) as u64))
+                        });
+                        run_no += 1;
+                        if progress {
+                            // adaptive runs can stop early, so the count is an upper bound there
+                            let mut line = format!("[{}/{}] {} N={} rep={}/{}", run_no, total, s.name(), n, rep, max_reps);
+                            if let Some(eta) = &eta { line = format!("{} {}", line, eta); }
+                            if progress_tty { eprint!("\r{}\x1b[K", line); } else { eprintln!("{}", line); }
+                        } else if let Some(eta) = eta.filter(|_| progress_tty) {
+                            eprint!("\r{}\x1b[K", eta);
+                        }
+                        let mut arr = make(n);
+                        // every scenario starts with `arr.init`, so only impls that fill in place can be covered
+                        let stable = arr.stable_region();
+                        if (mlocked || hugepages) && !stable && uncovered_warned.insert(arr.name()) {
+                            eprintln!("warning: {} reallocates on init; --mlocked/--hugepages do not cover it", arr.name());
+                        }
+                        #[cfg(unix)]
+                        let _lock = if mlocked && stable {
+                            MemLock::new(arr.as_mut()).unwrap_or_else(|e| {
+                                if !mlock_warned { eprintln!("warning: mlock failed ({}); continuing without locking", e); }
+                                mlock_warned = true;
+                                None
+                            })
+                        } else { None };
+                        if hugepages && stable {
+                            if let Err(e) = advise_hugepages(arr.as_mut()) {
+                                if !hugepages_warned { eprintln!("warning: madvise(MADV_HUGEPAGE) failed ({}); continuing with normal pages", e); }
+                                hugepages_warned = true;
+                            }
+                        }
+                        let faults_before = fault_counts();
+                        let io_before = io_counts();
+                        let wall = Instant::now();
+                        let res = s.run(arr.as_mut(), n, seed);
+                        last_wall_ms = wall.elapsed().as_millis();
+                        // io first: the /proc/self/stat read below would otherwise show up in rchar
+                        let io_after = io_counts();
+                        let faults_after = fault_counts();
+                        // sampled while arr is still mapped, keeping the peak across the run
+                        if hugepages {
+                            if let Some(kb) = anon_huge_kb() { hugepages_kb = Some(hugepages_kb.map_or(kb, |m| m.max(kb))); }
+                        }
+                        // an impl that cannot run the scenario will not manage on a later rep either
+                        let Some((ops, tot, nspop, initns)) = res else { break };
+                        name = arr.name();
+                        samples.push((ops, tot, nspop));
+                        let mut row = vec![
+                            now_iso(), arr.name().to_string(), s.name().to_string(),
+                            format!("{}", n), format!("{}", seed), format!("{}", rep),
+                            format!("{}", ops), format!("{}", tot), format!("{:.4}", nspop),
+                            format!("{}", initns), format!("{}", arr.relocations()), format!("{}", arr.conversions())
+                        ];
+                        // impls without a flat backing region (the maps, the deque) count as 8-byte elements
+                        let elem = arr.region().filter(|_| n > 0).map_or(8.0, |(_, len)| len as f64 / n as f64);
+                        let gbs = throughput_gbs(s.bytes_per_op(n, elem), ops, tot);
+                        set_col(&mut row, "throughput_gbs", format!("{:.4}", gbs));
+                        if let Some(peak) = peak_bw_gbs.filter(|_| gbs >= 0.0) {
+                            set_col(&mut row, "pct_of_peak_bandwidth", format!("{:.2}", 100.0 * gbs / peak));
+                        }
+                        if let (Some((min0, maj0)), Some((min1, maj1))) = (faults_before, faults_after) {
+                            set_col(&mut row, "minor_faults_delta", format!("{}", min1 - min0));
+                            set_col(&mut row, "major_faults_delta", format!("{}", maj1 - maj0));
+                        }
+                        if let (Some((r0, w0)), Some((r1, w1))) = (io_before, io_after) {
+                            set_col(&mut row, "proc_io_rchar_delta", format!("{}", r1 - r0));
+                            set_col(&mut row, "proc_io_wchar_delta", format!("{}", w1 - w0));
+                        }
+                        if checksum {
+                            // after the counters were read, so the extra reads do not show up in conversions_count
+                            let cs = (0..n).fold(0i64, |a, i| a ^ arr.read(i));
+                            set_col(&mut row, "checksum", format!("{}", cs));
+                            match first_checksum {
+                                None => first_checksum = Some(cs),
+                                Some(c0) if c0 != cs => eprintln!("warning: {} {} N={} seed={} rep {} checksum {} differs from the first rep ({})",
+                                                                  arr.name(), s.name(), n, seed, rep, cs, c0),
+                                Some(_) => {}
+                            }
+                        }
+                        if emit_intermediate {
+                            // fixed `key=value` layout so the lines can be grepped or split on whitespace
+                            println!("{} {} N={} seed={} rep={}: {:.4} ns/op", arr.name(), s.name(), n, seed, rep, nspop);
+                        }
+                        wtr.write_row(&row)?;
+                        // flush per rep so a killed run still leaves every finished row on disk
+                        wtr.flush()?;
+                        if let Some(i) = if verify { s.verify(arr.as_ref(), n) } else { None } {
+                            eprintln!("WARNING: VERIFICATION FAILED: {} {} N={} rep={}: element {} is {}",
+                                      arr.name(), s.name(), n, rep, i, arr.read(i));
+                            verify_failed = true;
+                        }
+                    }
+                    // the rep cap, not the budget, ended the group: say so rather than report a short run as met
+                    let measured_ns: i64 = samples.iter().map(|x| x.1).sum();
+                    if min_runtime_ms > 0 && samples.len() == max_reps && (measured_ns as u64) < min_runtime_ms * 1_000_000 {
+                        let note = format!("{} {} N={} seed={}: {:.2} of {} ms after {} reps",
+                                           name, s.name(), n, seed, measured_ns as f64 / 1e6, min_runtime_ms, max_reps);
+                        eprintln!("warning: --min-runtime-ms not reached for {}; raise --reps-max or --repeat-inner", note);
+                        wtr.write_row(&meta_row("min_runtime_shortfall", &note))?;
+                        wtr.flush()?;
+                    }
+                    if let Some(c) = clock_ns.filter(|_| !samples.is_empty()) {
+                        // total_time_ns is what the two `Instant::now()` calls bracket; ns_per_op is 0 for
+                        // INIT_* and GB/s for INIT_FILL_COPY_FROM_SLICE, so it says nothing about the timer
+                        let mean_total = samples.iter().map(|x| x.1 as f64).sum::<f64>() / samples.len() as f64;
+                        if mean_total < 10.0 * c as f64 {
+                            eprintln!("warning: {} {} N={} seed={}: {:.0} ns per rep is within 10x of the {} ns clock overhead; timing may be unreliable",
+                                      name, s.name(), n, seed, mean_total, c);
+                        }
+                    }
+                    if compare_csv.is_some() {
+                        let per_op: Vec<f64> = samples.iter().filter_map(|x| time_per_op(x.1, x.0)).collect();
+                        if !per_op.is_empty() {
+                            let mean = per_op.iter().sum::<f64>() / per_op.len() as f64;
+                            current_means.push(((name.to_string(), s.name().to_string(), n, seed), mean));
+                        }
+                    }
+                    if summary && !samples.is_empty() {
+                        let tots: Vec<i64> = samples.iter().map(|x| x.1).collect();
+                        let (mean, sd, min, max) = summarize(&tots);
+                        let mean_nspop = samples.iter().map(|x| x.2).sum::<f64>() / samples.len() as f64;
+                        let mut pcts = vec![String::new(); 3];
+                        if samples.len() >= 5 {
+                            let mut v: Vec<f64> = samples.iter().map(|x| x.2).collect();
+                            v.sort_by(f64::total_cmp);
+                            for (slot, p) in pcts.iter_mut().zip([50.0, 95.0, 99.0]) { *slot = format!("{:.4}", percentile(&v, p)); }
+                        }
+                        let mut row = vec![
+                            now_iso(), format!("summary_{}", name), s.name().to_string(),
+                            format!("{}", n), format!("{}", seed), "summary".to_string(),
+                            format!("{}", samples[0].0), format!("{}", mean.round() as i64), format!("{:.4}", mean_nspop),
+                            String::new(), String::new(), String::new(),
+                            format!("{:.1}", mean), format!("{:.1}", sd), format!("{}", min), format!("{}", max),
+                        ];
+                        row.extend(pcts);
+                        set_col(&mut row, "rep_count", format!("{}", samples.len()));
+                        summaries.push(row);
+                    }
                 }
             }
         }
     }
-    wtr.flush()?;
+    if hugepages {
+        wtr.write_row(&meta_row("hugepages_granted_kb", &hugepages_kb.map_or(String::new(), |kb| kb.to_string())))?;
+    }
+    if progress_tty && (progress || run_no > 1) { eprintln!(); }
+    for row in &summaries { wtr.write_row(row)?; }
+    wtr.finish()?;
     println!("Wrote {}", outfile);
+    if let Some(path) = &compare_csv {
+        let (regressed, matched) = compare_report(&baseline, &current_means, regression_threshold);
+        if matched == 0 {
+            eprintln!("no run matched a baseline row in {} by (impl, scenario, N, seed); nothing was compared", path);
+            std::process::exit(1);
+        }
+        if regressed {
+            eprintln!("regression beyond {:.1}% against {}", 100.0 * regression_threshold, path);
+            std::process::exit(1);
+        }
+    }
+    if verify_failed { std::process::exit(1); }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sizes_cases() {
+        let cases: &[(&str, &[usize])] = &[
+            ("", &[]),
+            ("0", &[0]),
+            ("0k", &[0]),
+            ("1G", &[1_000_000_000]),
+            ("1g", &[1_000_000_000]),
+            ("1.5m", &[1_500_000]),
+            ("2K,3M", &[2_000, 3_000_000]),
+            ("10000,", &[10_000]),
+            (",,5", &[5]),
+            (" 1k , 2k ", &[1_000, 2_000]),
+            ("1k,10k,100k,1m", &[1_000, 10_000, 100_000, 1_000_000]),
+            ("2T", &[2_000_000_000_000]),
+            ("8192B,8192b", &[8192, 8192]),
+            ("1kp,1mp,1KP", &[1024, 1_048_576, 1024]),
+            ("1.5mp", &[1_572_864]),
+            ("2gp,1tp", &[1 << 31, 1 << 40]),
+        ];
+        for &(input, want) in cases {
+            assert_eq!(parse_sizes(input, false).unwrap(), want, "lenient {:?}", input);
+            assert_eq!(parse_sizes(input, true).unwrap(), want, "strict {:?}", input);
+        }
+    }
+
+    #[test]
+    fn parse_sizes_garbage() {
+        let cases: &[(&str, &[usize])] = &[
+            ("abc", &[]),
+            ("1k,abc,2k", &[1_000, 2_000]),
+            ("k", &[]),
+            ("1x", &[]),
+            ("-5", &[]),
+            ("nan,inf", &[]),
+            ("1p", &[]),
+            ("1bp", &[]),
+            ("1kpp", &[]),
+            ("1kb", &[]),
+        ];
+        for &(input, want) in cases {
+            assert_eq!(parse_sizes(input, false).unwrap(), want, "lenient {:?}", input);
+            assert!(parse_sizes(input, true).is_err(), "strict {:?}", input);
+        }
+    }
+
+    #[test]
+    fn parse_size_round_trip() {
+        for n in [0usize, 1, 999, 1_000, 65_536, 1_000_000, 123_456_789] {
+            assert_eq!(parse_sizes(&n.to_string(), true).unwrap(), vec![n]);
+        }
+    }
+
+    #[test]
+    fn register_scenarios_dispatches() {
+        let s = select_scenarios("FILL_SEVENS", &ScenarioOpts::default()).unwrap();
+        assert_eq!(s.len(), 1);
+        let mut arr = TypedVecImpl::<i64>::new(4);
+        assert_eq!(s[0].run(&mut arr, 4, 0).unwrap().0, 4);
+        assert_eq!(arr.read(3), 7);
+    }
+
+    #[test]
+    fn impl_names_match() {
+        for (name, make) in impl_factories(&std::env::temp_dir()) {
+            assert_eq!(make(0).name(), name);
+        }
+    }
+
+    #[test]
+    fn seed_range_bounds() {
+        assert_eq!(parse_seed_range("42..45").unwrap(), vec![42, 43, 44]);
+        assert_eq!(parse_seed_range("0..1000").unwrap().len(), 1000);
+        for bad in ["5..5", "6..5", "0..1001", "1-3", "a..b", "..3"] {
+            assert!(parse_seed_range(bad).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn outfile_template_fields() {
+        let at = chrono::DateTime::parse_from_rfc3339("2024-03-05T07:08:09Z").unwrap().with_timezone(&Utc);
+        assert_eq!(expand_outfile_template("results-%Y%m%d-%H%M%S.csv", at), "results-20240305-070809.csv");
+        assert_eq!(expand_outfile_template("%Y/%Y-%j.csv", at), "2024/2024-%j.csv");
+    }
+
+    #[test]
+    fn pow2_range() {
+        assert_eq!(parse_pow2_range("1k..8kp").unwrap(), vec![1024, 2048, 4096, 8192]);
+        assert_eq!(parse_pow2_range("0..4").unwrap(), vec![1, 2, 4]);
+        assert_eq!(parse_pow2_range("5..7").unwrap(), Vec::<usize>::new());
+        for bad in ["8..4", "1k", "x..1k"] {
+            assert!(parse_pow2_range(bad).is_err(), "{:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,1073 @@
+// Benchmark scenarios. Each one is a `Scenario` that fills an impl, times its access pattern and
+// reports (ops, total_ns, ns_per_op, init_ns), where init_ns is the untimed-for-ops setup fill
+// (`arr.init` or the equivalent write loop); `all_scenarios` is the default run list.
+
+use crate::ArrayImpl;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::time::Instant;
+
+// `std::hint::black_box` when BB, else the identity; scenarios using it take `BB` as a const
+// parameter so --no-black-box is chosen once when the list is built, not inside the timed loops
+#[inline(always)]
+fn bb<const BB: bool, T>(x: T) -> T {
+    if BB { std::hint::black_box(x) } else { x }
+}
+
+pub(crate) trait Scenario {
+    fn name(&self) -> &str;
+    // None when `arr` lacks what the scenario needs (a slice view, exact indices, atomics)
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)>;
+    // Bytes touched per reported op, or None where it is ill-defined (e.g. re-touching hot lines);
+    // `elem` is the impl's bytes per element, so u8/i32/f32 impls are not credited with 8
+    fn bytes_per_op(&self, _n: usize, elem: f64) -> Option<f64> { Some(elem) }
+    // for --verify: first index holding the wrong value after `run`; scenarios without a check pass
+    fn verify(&self, _arr: &dyn ArrayImpl, _n: usize) -> Option<usize> { None }
+}
+
+fn rand_val(rng: &mut StdRng) -> i64 { (rng.gen_range(0..2001) as i64) - 1000 }
+
+fn mk_idx(rng: &mut StdRng, m: usize, n: usize) -> Vec<usize> {
+    (0..m).map(|_| rng.gen_range(0..n)).collect()
+}
+
+fn mk_perm(rng: &mut StdRng, n: usize) -> Vec<usize> {
+    use rand::seq::SliceRandom;
+    let mut p: Vec<usize> = (0..n).collect();
+    p.shuffle(rng);
+    p
+}
+
+// How far ahead of the current index the --prefetch loops issue their hint.
+const PREFETCH_DISTANCE: usize = 8;
+
+#[cfg(target_arch = "x86_64")]
+fn prefetch_t0(p: *const u8) {
+    use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+    // a prefetch never faults, so any address is fine
+    unsafe { _mm_prefetch::<_MM_HINT_T0>(p as *const i8) }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn prefetch_t0(_p: *const u8) {}
+
+// Base pointer and element width of an impl's memory when --prefetch is on and it has one.
+fn prefetch_base(arr: &mut dyn ArrayImpl, n: usize, prefetch: bool) -> Option<(*const u8, usize)> {
+    if !prefetch || n == 0 { return None; }
+    arr.region().map(|(p, len)| (p as *const u8, len / n))
+}
+
+// Knobs for individual scenarios, set from the command line.
+#[derive(Default)]
+pub(crate) struct ScenarioOpts {
+    // SORT_THEN_SEARCH lookups; None means min(1M, n)
+    pub(crate) sort_queries: Option<usize>,
+    // WRITE_ZIGZAG direction-alternating passes; None means 4
+    pub(crate) zigzag_passes: Option<usize>,
+    // software prefetch in READ_UNWRITTEN and WRITE_RANDOM (x86_64 only)
+    pub(crate) prefetch: bool,
+    // --repeat-inner: passes over the timed loop per recorded elapsed time (0 counts as 1); the
+    // per-op scenarios over a fixed index list or sweep honour it and report k x m ops, the rest
+    // run their loop once
+    pub(crate) repeat_inner: usize,
+    // --no-black-box: build the scenarios with `bb` as the identity
+    pub(crate) no_black_box: bool,
+}
+
+// The INIT_* scenarios report a single op covering the whole array.
+struct InitOnly;
+impl Scenario for InitOnly {
+    fn name(&self) -> &str { "INIT_ONLY" }
+    fn run(&self, arr: &mut dyn ArrayImpl, _n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let t0 = Instant::now();
+        arr.init(42);
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((1, el, 0.0, el))
+    }
+    fn bytes_per_op(&self, n: usize, elem: f64) -> Option<f64> { Some(elem * n as f64) }
+}
+
+struct InitMemset<const BB: bool>;
+impl<const BB: bool> Scenario for InitMemset<BB> {
+    fn name(&self) -> &str { "INIT_MEMSET" }
+    fn run(&self, arr: &mut dyn ArrayImpl, _n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let a = arr.slice_mut()?;
+        let t0 = Instant::now();
+        unsafe { std::ptr::write_bytes(a.as_mut_ptr(), 0u8, a.len()); }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        Some((1, el, 0.0, el))
+    }
+    fn bytes_per_op(&self, n: usize, elem: f64) -> Option<f64> { Some(elem * n as f64) }
+}
+
+struct InitFillCopyFromSlice<const BB: bool>;
+impl<const BB: bool> Scenario for InitFillCopyFromSlice<BB> {
+    fn name(&self) -> &str { "INIT_FILL_COPY_FROM_SLICE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, _n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let a = arr.slice_mut()?;
+        let template = vec![42i64; a.len()];
+        let t0 = Instant::now();
+        a.copy_from_slice(&template);
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        // the ns_per_op slot carries copy throughput in GB/s (bytes per ns) for this scenario
+        let gbs = std::mem::size_of_val(a) as f64 / el as f64;
+        Some((1, el, gbs, el))
+    }
+    fn bytes_per_op(&self, n: usize, elem: f64) -> Option<f64> { Some(elem * n as f64) }
+}
+
+// MEMSET_PARTIAL_<P>: INIT_MEMSET over only the leading P% of the slice; the tail keeps its contents
+struct MemsetPartial<const BB: bool> { name: String, pct: usize }
+impl<const BB: bool> MemsetPartial<BB> {
+    fn new(pct: usize) -> Self { Self { name: format!("MEMSET_PARTIAL_{}", pct), pct } }
+    fn len(&self, n: usize) -> usize { n * self.pct / 100 }
+}
+impl<const BB: bool> Scenario for MemsetPartial<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let a = arr.slice_mut()?;
+        let k = self.len(n);
+        let t0 = Instant::now();
+        unsafe { std::ptr::write_bytes(a.as_mut_ptr(), 0u8, k); }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        Some((1, el, 0.0, el))
+    }
+    fn bytes_per_op(&self, n: usize, elem: f64) -> Option<f64> { Some(elem * self.len(n) as f64) }
+}
+
+// STREAM-style copy from a random source into the impl's memory, one op per element
+struct CopyArray<const BB: bool>;
+impl<const BB: bool> Scenario for CopyArray<BB> {
+    fn name(&self) -> &str { "COPY_ARRAY" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let dst = arr.slice_mut()?;
+        let src: Vec<i64> = (0..n).map(|_| rng.gen()).collect();
+        let t0 = Instant::now();
+        dst.copy_from_slice(&src);
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(dst.as_ptr());
+        Some((n, el, el as f64 / n as f64, 0))
+    }
+    // one load plus one store per element, counted the way STREAM Copy does
+    fn bytes_per_op(&self, _n: usize, elem: f64) -> Option<f64> { Some(2.0 * elem) }
+}
+
+// ROTATE_<k>: slice::rotate_left(k % n) over an index fill; ROTATE_N2 rotates by n/2, swapping halves
+struct Rotate<const BB: bool> { name: String, k: Option<usize> }
+impl<const BB: bool> Rotate<BB> {
+    fn new(k: Option<usize>) -> Self {
+        let name = match k { Some(k) => format!("ROTATE_{}", k), None => "ROTATE_N2".to_string() };
+        Self { name, k }
+    }
+}
+impl<const BB: bool> Scenario for Rotate<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let a = arr.slice_mut()?;
+        let t0 = Instant::now();
+        for (i, x) in a.iter_mut().enumerate() { *x = i as i64; }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let k = match self.k { Some(k) => k.checked_rem(n).unwrap_or(0), None => n / 2 };
+        let t0 = Instant::now();
+        a.rotate_left(bb::<BB, _>(k));
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+    // every element is loaded and stored at least once
+    fn bytes_per_op(&self, _n: usize, elem: f64) -> Option<f64> { Some(2.0 * elem) }
+}
+
+// slice::reverse over an index fill: the pairwise half swap behind ROTATE_N2, under its own name
+struct Reverse<const BB: bool>;
+impl<const BB: bool> Scenario for Reverse<BB> {
+    fn name(&self) -> &str { "REVERSE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let a = arr.slice_mut()?;
+        let t0 = Instant::now();
+        for (i, x) in a.iter_mut().enumerate() { *x = i as i64; }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let t0 = Instant::now();
+        bb::<BB, _>(&mut *a).reverse();
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+    // each element is loaded once and stored once
+    fn bytes_per_op(&self, _n: usize, elem: f64) -> Option<f64> { Some(2.0 * elem) }
+}
+
+struct ReduceSum<const BB: bool> { repeat: usize }
+impl<const BB: bool> Scenario for ReduceSum<BB> {
+    fn name(&self) -> &str { "REDUCE_SUM" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(1);
+        let a = arr.slice_mut()?;
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..k { s = s.wrapping_add(bb::<BB, _>(&*a).iter().sum()); }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((k * n, el, el as f64 / (k * n) as f64, init_ns))
+    }
+}
+
+struct ReadUnwritten<const BB: bool> { prefetch: bool, repeat: usize }
+impl<const BB: bool> Scenario for ReadUnwritten<BB> {
+    fn name(&self) -> &str { "READ_UNWRITTEN" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(123);
+        let m = std::cmp::min(1_000_000usize, 10*n);
+        let idx = mk_idx(&mut rng, m, n);
+        let pf = prefetch_base(arr, n, self.prefetch);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        // picked once, so the default loop is the plain one with no per-op prefetch check
+        match pf {
+            None => for _ in 0..k { for &j in &idx { s = s.wrapping_add(arr.read(bb::<BB, _>(j))); } },
+            Some((p, w)) => for _ in 0..k {
+                for (t, &j) in idx.iter().enumerate() {
+                    if let Some(&ahead) = idx.get(t + PREFETCH_DISTANCE) { prefetch_t0(p.wrapping_add(ahead * w)); }
+                    s = s.wrapping_add(arr.read(bb::<BB, _>(j)));
+                }
+            },
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+}
+
+// READ_UNWRITTEN after a seeded random fill instead of the constant one
+struct PrefillRandom<const BB: bool> { repeat: usize }
+impl<const BB: bool> Scenario for PrefillRandom<BB> {
+    fn name(&self) -> &str { "PREFILL_RANDOM" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let vals: Vec<i64> = (0..n).map(|_| rng.gen()).collect();
+        let t0 = Instant::now();
+        for (i, &v) in vals.iter().enumerate() { arr.write(i, v); }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let m = std::cmp::min(1_000_000usize, 10*n);
+        let idx = mk_idx(&mut rng, m, n);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..k { for &j in &idx { s = s.wrapping_add(arr.read(bb::<BB, _>(j))); } }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+}
+
+// READ_UNWRITTEN's random reads confined to [0, n/10), so the working set stays cache-sized longer
+struct ReadHotSegment<const BB: bool> { repeat: usize }
+impl<const BB: bool> Scenario for ReadHotSegment<BB> {
+    fn name(&self) -> &str { "READ_HOT_SEGMENT" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(123);
+        let m = std::cmp::min(1_000_000usize, 10*n);
+        let idx = mk_idx(&mut rng, m, std::cmp::max(1usize, n/10));
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..k { for &j in &idx { s = s.wrapping_add(arr.read(bb::<BB, _>(j))); } }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+struct WriteSequential { repeat: usize }
+impl Scenario for WriteSequential {
+    fn name(&self) -> &str { "WRITE_SEQUENTIAL" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(0);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        for _ in 0..k { for i in 0..n { arr.write(i, i as i64); } }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((k * n, el, el as f64 / (k * n) as f64, init_ns))
+    }
+    fn verify(&self, arr: &dyn ArrayImpl, n: usize) -> Option<usize> {
+        if !arr.holds_indices(n) { return None; }
+        (0..n).find(|&i| arr.read(i) != i as i64)
+    }
+}
+
+// WRITE_SEQUENTIAL_CHUNKED_<C>: the same sweep as C-element `fill` calls, one op per chunk
+struct WriteSequentialChunked<const BB: bool> { name: String, chunk: usize }
+impl<const BB: bool> WriteSequentialChunked<BB> {
+    fn new(chunk: usize) -> Self { Self { name: format!("WRITE_SEQUENTIAL_CHUNKED_{}", chunk), chunk } }
+}
+impl<const BB: bool> Scenario for WriteSequentialChunked<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(0);
+        let a = arr.slice_mut()?;
+        let m = n.div_ceil(self.chunk);
+        let t0 = Instant::now();
+        for (c, part) in a.chunks_mut(self.chunk).enumerate() { part.fill(bb::<BB, _>(c as i64)); }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, elem: f64) -> Option<f64> { Some(elem * self.chunk as f64) }
+}
+
+struct ReverseSequential { repeat: usize }
+impl Scenario for ReverseSequential {
+    fn name(&self) -> &str { "REVERSE_SEQUENTIAL" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(0);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        for _ in 0..k { for i in (0..n).rev() { arr.write(i, i as i64); } }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((k * n, el, el as f64 / (k * n) as f64, init_ns))
+    }
+}
+
+// odd passes run forward, even passes backward, so the stream reverses at each end
+struct WriteZigzag { passes: usize }
+impl Scenario for WriteZigzag {
+    fn name(&self) -> &str { "WRITE_ZIGZAG" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(0);
+        let t0 = Instant::now();
+        for pass in 0..self.passes {
+            if pass % 2 == 0 { for i in 0..n { arr.write(i, i as i64); } }
+            else { for i in (0..n).rev() { arr.write(i, i as i64); } }
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        let ops = self.passes * n;
+        Some((ops, el, el as f64 / ops.max(1) as f64, init_ns))
+    }
+}
+
+struct WriteRandom<const BB: bool> { prefetch: bool, repeat: usize }
+impl<const BB: bool> Scenario for WriteRandom<BB> {
+    fn name(&self) -> &str { "WRITE_RANDOM" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(0);
+        let m = std::cmp::min(1_000_000usize, n);
+        let idx = mk_idx(&mut rng, m, n);
+        let pf = prefetch_base(arr, n, self.prefetch);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        // picked once, as in READ_UNWRITTEN
+        match pf {
+            None => for _ in 0..k { for &j in &idx { arr.write(bb::<BB, _>(j), rand_val(&mut rng)); } },
+            Some((p, w)) => for _ in 0..k {
+                for (t, &j) in idx.iter().enumerate() {
+                    if let Some(&ahead) = idx.get(t + PREFETCH_DISTANCE) { prefetch_t0(p.wrapping_add(ahead * w)); }
+                    arr.write(bb::<BB, _>(j), rand_val(&mut rng));
+                }
+            },
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+}
+
+struct ReadAfterWrite<const BB: bool> { repeat: usize }
+impl<const BB: bool> Scenario for ReadAfterWrite<BB> {
+    fn name(&self) -> &str { "READ_AFTER_WRITE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(0);
+        let m = std::cmp::min(1_000_000usize, n);
+        let idx = mk_idx(&mut rng, m, n);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..k {
+            for &j in &idx {
+                arr.write(bb::<BB, _>(j), rand_val(&mut rng));
+                s = s.wrapping_add(arr.read(bb::<BB, _>(j)));
+            }
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((2 * k * m, el, el as f64 / (2 * k * m) as f64, init_ns))
+    }
+}
+
+// untimed writes to m random indices, then timed reads of the same indices: is the set still cached?
+struct WriteThenReadSameIndices<const BB: bool> { repeat: usize }
+impl<const BB: bool> Scenario for WriteThenReadSameIndices<BB> {
+    fn name(&self) -> &str { "WRITE_THEN_READ_SAME_INDICES" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(0);
+        let m = std::cmp::min(1_000_000usize, n);
+        let idx = mk_idx(&mut rng, m, n);
+        for &j in &idx { arr.write(bb::<BB, _>(j), rand_val(&mut rng)); }
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..k { for &j in &idx { s = s.wrapping_add(arr.read(bb::<BB, _>(j))); } }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+}
+
+// 50/50 like MIXED_R50W50, but in runs of BURST reads then BURST writes
+struct MixedBursty<const BB: bool> { repeat: usize }
+impl<const BB: bool> Scenario for MixedBursty<BB> {
+    fn name(&self) -> &str { "MIXED_BURSTY" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        const BURST: usize = 100;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(42);
+        let m = std::cmp::min(1_000_000usize, n);
+        let idx = mk_idx(&mut rng, m, n);
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut ssum: i64 = 0;
+        for _ in 0..k {
+            for (t, &j) in idx.iter().enumerate() {
+                if (t / BURST).is_multiple_of(2) { ssum = ssum.wrapping_add(arr.read(bb::<BB, _>(j))); }
+                else { arr.write(bb::<BB, _>(j), rand_val(&mut rng)); }
+            }
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(ssum);
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+}
+
+// Vec::retain dropping every other element of an alternating 0/1 fill; len is restored untimed
+struct FilterRetain<const BB: bool>;
+impl<const BB: bool> Scenario for FilterRetain<BB> {
+    fn name(&self) -> &str { "FILTER_RETAIN" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let v = arr.vec_mut()?;
+        let t0 = Instant::now();
+        for (i, x) in v.iter_mut().enumerate() { *x = (i & 1) as i64; }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let t0 = Instant::now();
+        v.retain(|&x| x != 0);
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(v.len());
+        v.resize(n, 0);
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+}
+
+// Vec::dedup over a sorted rand_val fill (about 2001 distinct values); the sort is reported as init
+struct Dedup<const BB: bool>;
+impl<const BB: bool> Scenario for Dedup<BB> {
+    fn name(&self) -> &str { "DEDUP" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let v = arr.vec_mut()?;
+        let t0 = Instant::now();
+        for x in v.iter_mut() { *x = rand_val(&mut rng); }
+        v.sort_unstable();
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let t0 = Instant::now();
+        v.dedup();
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(v.len());
+        v.resize(n, 0);
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+}
+
+// MIXED_R<r>W<w>: each op independently reads with probability r%
+struct Mixed<const BB: bool> { name: String, read_pct: i32, repeat: usize }
+impl<const BB: bool> Mixed<BB> {
+    fn new(r: i32, w: i32, repeat: usize) -> Self { Self { name: format!("MIXED_R{}W{}", r, w), read_pct: r, repeat } }
+}
+impl<const BB: bool> Scenario for Mixed<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(42);
+        let m = std::cmp::min(1_000_000usize, n);
+        let idx = mk_idx(&mut rng, m, n);
+        let ops: Vec<u8> = (0..m).map(|_| if rng.gen_range(0..100) < self.read_pct {0} else {1}).collect();
+
+        let k = self.repeat;
+        let t0 = Instant::now();
+        let mut ssum: i64 = 0;
+        for _ in 0..k {
+            for t in 0..m {
+                if ops[t] == 0 { ssum = ssum.wrapping_add(arr.read(bb::<BB, _>(idx[t]))); }
+                else { arr.write(bb::<BB, _>(idx[t]), rand_val(&mut rng)); }
+            }
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(ssum);
+        Some((k * m, el, el as f64 / (k * m) as f64, init_ns))
+    }
+}
+
+struct AdversarialHotspot<const BB: bool>;
+impl<const BB: bool> Scenario for AdversarialHotspot<BB> {
+    fn name(&self) -> &str { "ADVERSARIAL_HOTSPOT" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(0);
+        let m = std::cmp::min(1_000_000usize, n);
+        let t0 = Instant::now();
+        for _ in 0..m {
+            let j = hotspot_idx(&mut rng, n);
+            arr.write(bb::<BB, _>(j), rand_val(&mut rng));
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+// half of the accesses land in the hot segment [0, n/10), the rest anywhere in [0, n)
+fn hotspot_idx(rng: &mut StdRng, n: usize) -> usize {
+    let hot = std::cmp::max(1usize, n/10);
+    if rng.gen_range(0..2) == 0 { rng.gen_range(0..hot) } else { rng.gen_range(0..n) }
+}
+
+struct AdversarialHotspotRead<const BB: bool>;
+impl<const BB: bool> Scenario for AdversarialHotspotRead<BB> {
+    fn name(&self) -> &str { "ADVERSARIAL_HOTSPOT_READ" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(42);
+        let m = std::cmp::min(1_000_000usize, n);
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..m {
+            let j = hotspot_idx(&mut rng, n);
+            s = s.wrapping_add(arr.read(bb::<BB, _>(j)));
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+// each op independently reads or writes with probability 1/2, same hotspot bias
+struct AdversarialHotspotMixed<const BB: bool>;
+impl<const BB: bool> Scenario for AdversarialHotspotMixed<BB> {
+    fn name(&self) -> &str { "ADVERSARIAL_HOTSPOT_MIXED" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(42);
+        let m = std::cmp::min(1_000_000usize, n);
+        let t0 = Instant::now();
+        let mut s: i64 = 0;
+        for _ in 0..m {
+            let j = hotspot_idx(&mut rng, n);
+            if rng.gen_range(0..2) == 0 { s = s.wrapping_add(arr.read(bb::<BB, _>(j))); }
+            else { arr.write(bb::<BB, _>(j), rand_val(&mut rng)); }
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(s);
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+struct AdversarialColdspot<const BB: bool>;
+impl<const BB: bool> Scenario for AdversarialColdspot<BB> {
+    fn name(&self) -> &str { "ADVERSARIAL_COLDSPOT" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(0);
+        let m = std::cmp::min(1_000_000usize, n);
+        let cold = std::cmp::min(n/10, n.saturating_sub(1));
+        let t0 = Instant::now();
+        for _ in 0..m {
+            let j = if rng.gen_range(0..10) != 0 { rng.gen_range(cold..n) } else { rng.gen_range(0..n) };
+            arr.write(bb::<BB, _>(j), rand_val(&mut rng));
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+struct PointerChase<const BB: bool>;
+impl<const BB: bool> Scenario for PointerChase<BB> {
+    fn name(&self) -> &str { "POINTER_CHASE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        if !arr.holds_indices(n) { return None; }
+        let mut rng = StdRng::seed_from_u64(seed);
+        // link the shuffled order into a single cycle so the chase visits every cell
+        let order = mk_perm(&mut rng, n);
+        let t0 = Instant::now();
+        for k in 0..n { arr.write(order[k], order[(k + 1) % n] as i64); }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let t0 = Instant::now();
+        let mut j = 0usize;
+        for _ in 0..n { j = arr.read(bb::<BB, _>(j)) as usize; }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(j);
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+}
+
+// per i: gather tmp[i] = src[gather_idx[i]] out of the impl, then scatter dst[scatter_idx[i]] = tmp[i]
+// into a separate, pre-touched i64 buffer
+struct ScatterGather<const BB: bool>;
+impl<const BB: bool> Scenario for ScatterGather<BB> {
+    fn name(&self) -> &str { "SCATTER_GATHER" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let vals: Vec<i64> = (0..n).map(|_| rand_val(&mut rng)).collect();
+        let t0 = Instant::now();
+        for (i, &v) in vals.iter().enumerate() { arr.write(i, v); }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let gather_idx = mk_perm(&mut rng, n);
+        let scatter_idx = mk_perm(&mut rng, n);
+        let mut tmp = vec![0i64; n];
+        let mut dst = vec![1i64; n];
+        let t0 = Instant::now();
+        for i in 0..n {
+            tmp[i] = arr.read(bb::<BB, _>(gather_idx[i]));
+            dst[bb::<BB, _>(scatter_idx[i])] = tmp[i];
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>((tmp.as_ptr(), dst.as_ptr()));
+        let ops = 2 * n;
+        Some((ops, el, el as f64 / ops as f64, init_ns))
+    }
+}
+
+struct ScatterWrite<const BB: bool>;
+impl<const BB: bool> Scenario for ScatterWrite<BB> {
+    fn name(&self) -> &str { "SCATTER_WRITE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let init_ns = arr.init(0);
+        let idx = mk_perm(&mut rng, n);
+        let t0 = Instant::now();
+        for &j in &idx { arr.write(bb::<BB, _>(j), j as i64); }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+}
+
+// CACHE_THRASH_<k>: PASSES rounds of one sequential pass over each of k equal sub-ranges in turn,
+// so every sub-range is revisited after the other k-1 have had the chance to evict it
+struct CacheThrash<const BB: bool> { name: String, k: usize }
+impl<const BB: bool> CacheThrash<BB> {
+    const PASSES: usize = 4;
+    fn new(k: usize) -> Self { Self { name: format!("CACHE_THRASH_{}", k), k } }
+}
+impl<const BB: bool> Scenario for CacheThrash<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let k = self.k;
+        let len = n / k;
+        if len == 0 { return None; }
+        let init_ns = arr.init(0);
+        let m = Self::PASSES * k * len;
+        let t0 = Instant::now();
+        for _ in 0..Self::PASSES {
+            for part in 0..k {
+                for j in part * len..(part + 1) * len { arr.write(bb::<BB, _>(j), j as i64); }
+            }
+        }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+}
+
+struct DependentReadChain<const BB: bool>;
+impl<const BB: bool> Scenario for DependentReadChain<BB> {
+    fn name(&self) -> &str { "DEPENDENT_READ_CHAIN" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        if !arr.holds_indices(n) { return None; }
+        let mut rng = StdRng::seed_from_u64(seed);
+        // one cycle through every cell, as in POINTER_CHASE; a raw permutation would leave index 0
+        // on whatever (often cache-sized) cycle contains it
+        let order = mk_perm(&mut rng, n);
+        let t0 = Instant::now();
+        for k in 0..n { arr.write(order[k], order[(k + 1) % n] as i64); }
+        let init_ns = t0.elapsed().as_nanos() as i64;
+        let m = std::cmp::min(1_000_000usize, n);
+        let t0 = Instant::now();
+        let mut j = 0usize;
+        for _ in 0..m { j = (arr.read(bb::<BB, _>(j)) as usize) % n; }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(j);
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+}
+
+// sort time goes in the init slot, the binary searches are the measured ops
+struct SortThenSearch<const BB: bool> { queries: Option<usize> }
+impl<const BB: bool> Scenario for SortThenSearch<BB> {
+    fn name(&self) -> &str { "SORT_THEN_SEARCH" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let a = arr.slice_mut()?;
+        for x in a.iter_mut() { *x = rng.gen(); }
+        let t0 = Instant::now();
+        a.sort_unstable();
+        let sort_ns = t0.elapsed().as_nanos() as i64;
+        let m = self.queries.unwrap_or(std::cmp::min(1_000_000usize, n));
+        let queries: Vec<i64> = (0..m).map(|_| rng.gen()).collect();
+        let t0 = Instant::now();
+        let mut hits = 0usize;
+        for q in &queries { if a.binary_search(bb::<BB, _>(q)).is_ok() { hits += 1; } }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(hits);
+        Some((m, el, el as f64 / m as f64, sort_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+#[cfg(feature = "rayon")]
+struct ParallelWriteSequential<const BB: bool>;
+#[cfg(feature = "rayon")]
+impl<const BB: bool> Scenario for ParallelWriteSequential<BB> {
+    fn name(&self) -> &str { "PARALLEL_WRITE_SEQUENTIAL" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        use rayon::prelude::*;
+        let init_ns = arr.init(0);
+        let a = arr.slice_mut()?;
+        let chunk = std::cmp::max(1, n.div_ceil(rayon::current_num_threads()));
+        let t0 = Instant::now();
+        a.par_chunks_mut(chunk).enumerate().for_each(|(c, part)| {
+            let base = c * chunk;
+            for (k, x) in part.iter_mut().enumerate() { *x = (base + k) as i64; }
+        });
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(a.as_ptr());
+        Some((n, el, el as f64 / n as f64, init_ns))
+    }
+}
+
+#[cfg(feature = "rayon")]
+struct ParallelRandomWrite<const BB: bool>;
+#[cfg(feature = "rayon")]
+impl<const BB: bool> Scenario for ParallelRandomWrite<BB> {
+    fn name(&self) -> &str { "PARALLEL_RANDOM_WRITE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        use rayon::prelude::*;
+        use std::sync::atomic::Ordering;
+        let init_ns = arr.init(0);
+        let a = arr.atomics()?;
+        let t = rayon::current_num_threads();
+        let per = std::cmp::min(1_000_000usize, n) / t;
+        let m = per * t;
+        let t0 = Instant::now();
+        (0..t).into_par_iter().for_each(|tid| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(tid as u64));
+            for _ in 0..per {
+                let j = rng.gen_range(0..n);
+                a[bb::<BB, _>(j)].store(rand_val(&mut rng), Ordering::Relaxed);
+            }
+        });
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+}
+
+// every thread hammers its own slot; unpadded slots share cachelines, padded ones sit 64 bytes apart
+#[cfg(feature = "rayon")]
+struct FalseSharing<const BB: bool> { padded: bool }
+#[cfg(feature = "rayon")]
+impl<const BB: bool> Scenario for FalseSharing<BB> {
+    fn name(&self) -> &str { if self.padded { "FALSE_SHARING_PADDED" } else { "FALSE_SHARING" } }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        use rayon::prelude::*;
+        use std::sync::atomic::Ordering;
+        let init_ns = arr.init(0);
+        let a = arr.atomics()?;
+        let t = rayon::current_num_threads();
+        let gap = if self.padded { 64 / std::mem::size_of::<i64>() } else { 1 };
+        if t * gap > n { return None; }
+        let per = std::cmp::min(1_000_000usize, n);
+        let m = per * t;
+        let t0 = Instant::now();
+        (0..t).into_par_iter().for_each(|tid| {
+            let slot = &a[tid * gap];
+            for k in 0..per { slot.store(bb::<BB, _>(k as i64), Ordering::Relaxed); }
+        });
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+// one reader per rayon thread takes the read lock for every random read while a single writer
+// sweeps the array under the write lock until the readers are done; the Vec is moved into the
+// lock for the run and handed back afterwards
+#[cfg(feature = "rayon")]
+struct ConcurrentReaders<const BB: bool>;
+#[cfg(feature = "rayon")]
+impl<const BB: bool> Scenario for ConcurrentReaders<BB> {
+    fn name(&self) -> &str { "CONCURRENT_READERS" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> {
+        use std::sync::{Barrier, RwLock};
+        use std::sync::atomic::{AtomicBool, Ordering};
+        if n == 0 { return None; }
+        let init_ns = arr.init(0);
+        let v = arr.vec_mut()?;
+        let t = rayon::current_num_threads();
+        let per = std::cmp::min(1_000_000usize, n) / t;
+        let m = per * t;
+        let idxs: Vec<Vec<usize>> = (0..t).map(|tid| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(tid as u64));
+            mk_idx(&mut rng, per, n)
+        }).collect();
+        let lock = RwLock::new(std::mem::take(v));
+        let done = AtomicBool::new(false);
+        // the readers are spawned and hold their indices before they start their clocks, so only
+        // the lock-protected reads are timed
+        let start = Barrier::new(t + 1);
+        let el = std::thread::scope(|sc| {
+            let writer = sc.spawn(|| {
+                let mut i = 0;
+                while !done.load(Ordering::Relaxed) {
+                    lock.write().unwrap()[i] = i as i64;
+                    i = (i + 1) % n;
+                }
+            });
+            let readers: Vec<_> = idxs.into_iter().map(|idx| {
+                let (lock, start) = (&lock, &start);
+                sc.spawn(move || {
+                    start.wait();
+                    let t0 = Instant::now();
+                    let mut s: i64 = 0;
+                    for &j in &idx { s = s.wrapping_add(lock.read().unwrap()[bb::<BB, _>(j)]); }
+                    bb::<BB, _>(s);
+                    (t0, Instant::now())
+                })
+            }).collect();
+            start.wait();
+            // first reader start to last reader finish, as each thread saw it
+            let spans: Vec<(Instant, Instant)> = readers.into_iter().map(|r| r.join().unwrap()).collect();
+            let t0 = spans.iter().map(|s| s.0).min().unwrap();
+            let el = (spans.iter().map(|s| s.1).max().unwrap() - t0).as_nanos() as i64;
+            done.store(true, Ordering::Relaxed);
+            writer.join().unwrap();
+            el
+        });
+        *v = lock.into_inner().unwrap();
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    fn bytes_per_op(&self, _n: usize, _elem: f64) -> Option<f64> { None }
+}
+
+// The producer fills a CHUNK-element chunk and sends it, with its index, to a consumer thread that
+// sums it and acks; the next chunk is only written after the ack, so each op is one full
+// write -> hand-off -> read -> ack round trip with the chunk's lines migrating between cores.
+struct ChannelPipeline<const BB: bool>;
+impl<const BB: bool> ChannelPipeline<BB> { const CHUNK: usize = 1024; }
+impl<const BB: bool> Scenario for ChannelPipeline<BB> {
+    fn name(&self) -> &str { "CHANNEL_PIPELINE" }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        use std::sync::mpsc::channel;
+        let init_ns = arr.init(0);
+        let a = arr.slice_mut()?;
+        let m = n.div_ceil(Self::CHUNK);
+        let (to_consumer, from_producer) = channel::<(usize, &mut [i64])>();
+        let (to_producer, from_consumer) = channel::<usize>();
+        let el = std::thread::scope(|sc| {
+            sc.spawn(move || {
+                let mut s: i64 = 0;
+                for (c, part) in from_producer {
+                    s = s.wrapping_add(part.iter().sum());
+                    if to_producer.send(c).is_err() { break; }
+                }
+                bb::<BB, _>(s);
+            });
+            // after the spawn, so thread start-up is not charged to the first chunk
+            let t0 = Instant::now();
+            for (c, part) in a.chunks_mut(Self::CHUNK).enumerate() {
+                for (k, x) in part.iter_mut().enumerate() { *x = (c * Self::CHUNK + k) as i64; }
+                to_consumer.send((c, part)).unwrap();
+                bb::<BB, _>(from_consumer.recv().unwrap());
+            }
+            let el = t0.elapsed().as_nanos() as i64;
+            drop(to_consumer);
+            el
+        });
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+    // each chunk is written once and read once
+    fn bytes_per_op(&self, _n: usize, elem: f64) -> Option<f64> { Some(2.0 * elem * Self::CHUNK as f64) }
+}
+
+// READ_STRIDE_DEP_<k>: each step's offset comes from the value just loaded, so loads cannot overlap
+struct ReadStrideDep<const BB: bool> { name: String, stride: usize }
+impl<const BB: bool> ReadStrideDep<BB> {
+    fn new(stride: usize) -> Self { Self { name: format!("READ_STRIDE_DEP_{}", stride), stride } }
+}
+impl<const BB: bool> Scenario for ReadStrideDep<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(123);
+        let m = std::cmp::min(1_000_000usize, n);
+        let t0 = Instant::now();
+        let mut j = 0usize;
+        for _ in 0..m { j = (j + arr.read(bb::<BB, _>(j)).unsigned_abs() as usize % self.stride + 1) % n; }
+        let el = t0.elapsed().as_nanos() as i64;
+        bb::<BB, _>(j);
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+}
+
+// STRIDE_S<s>: sequential writes touching every s-th element
+struct Stride<const BB: bool> { name: String, stride: usize }
+impl<const BB: bool> Stride<BB> {
+    fn new(stride: usize) -> Self { Self { name: format!("STRIDE_S{}", stride), stride } }
+}
+impl<const BB: bool> Scenario for Stride<BB> {
+    fn name(&self) -> &str { &self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> Option<(usize, i64, f64, i64)> {
+        let init_ns = arr.init(0);
+        let m = n.div_ceil(self.stride);
+        let t0 = Instant::now();
+        for i in (0..n).step_by(self.stride) { arr.write(bb::<BB, _>(i), i as i64); }
+        let el = t0.elapsed().as_nanos() as i64;
+        Some((m, el, el as f64 / m as f64, init_ns))
+    }
+}
+
+pub(crate) type ScenarioFn = fn(&mut dyn ArrayImpl, usize, u64) -> (usize, i64, f64, i64);
+
+// `register_scenarios! { "MY_SCENARIO" => my_scenario_fn, ... }` defines `CUSTOM_SCENARIOS`, the
+// extra scenarios run after the built-in ones and selectable by name like them. Each function returns
+// (ops, total_ns, ns_per_op, init_ns); the `as ScenarioFn` coercion rejects any other signature.
+macro_rules! register_scenarios {
+    ($($name:expr => $f:path),* $(,)?) => {
+        static CUSTOM_SCENARIOS: &[(&str, ScenarioFn)] = &[$(($name, $f as ScenarioFn)),*];
+    };
+}
+
+// add entries here
+#[cfg(not(test))]
+register_scenarios! {}
+
+// test builds register one scenario for `register_scenarios_dispatches`
+#[cfg(test)]
+fn fill_sevens(arr: &mut dyn ArrayImpl, n: usize, _seed: u64) -> (usize, i64, f64, i64) {
+    for i in 0..n { arr.write(i, 7); }
+    (n, 0, 0.0, 0)
+}
+
+#[cfg(test)]
+register_scenarios! { "FILL_SEVENS" => fill_sevens }
+
+struct Registered { name: &'static str, f: ScenarioFn }
+impl Scenario for Registered {
+    fn name(&self) -> &str { self.name }
+    fn run(&self, arr: &mut dyn ArrayImpl, n: usize, seed: u64) -> Option<(usize, i64, f64, i64)> { Some((self.f)(arr, n, seed)) }
+}
+
+// Default run list. Parameterised families (MIXED_R<r>W<w>, STRIDE_S<s>, CACHE_THRASH_<k>, READ_STRIDE_DEP_<k>,
+// WRITE_SEQUENTIAL_CHUNKED_<C>, MEMSET_PARTIAL_<P>, ROTATE_<k>) appear with representative values; other values can be requested through --scenarios.
+pub(crate) fn all_scenarios(opts: &ScenarioOpts) -> Vec<Box<dyn Scenario>> {
+    if opts.no_black_box { default_scenarios::<false>(opts) } else { default_scenarios::<true>(opts) }
+}
+
+fn default_scenarios<const BB: bool>(opts: &ScenarioOpts) -> Vec<Box<dyn Scenario>> {
+    let repeat = opts.repeat_inner.max(1);
+    let mut v: Vec<Box<dyn Scenario>> = vec![
+        Box::new(InitOnly), Box::new(InitMemset::<BB>), Box::new(MemsetPartial::<BB>::new(10)), Box::new(MemsetPartial::<BB>::new(50)),
+        Box::new(InitFillCopyFromSlice::<BB>),
+        Box::new(CopyArray::<BB>), Box::new(ReduceSum::<BB> { repeat }), Box::new(Rotate::<BB>::new(Some(1))), Box::new(Rotate::<BB>::new(None)),
+        Box::new(Reverse::<BB>),
+        Box::new(ReadUnwritten::<BB> { prefetch: opts.prefetch, repeat }), Box::new(PrefillRandom::<BB> { repeat }), Box::new(ReadHotSegment::<BB> { repeat }),
+        Box::new(WriteSequential { repeat }), Box::new(WriteSequentialChunked::<BB>::new(8)), Box::new(ReverseSequential { repeat }),
+        Box::new(WriteZigzag { passes: opts.zigzag_passes.unwrap_or(4) }),
+        Box::new(WriteRandom::<BB> { prefetch: opts.prefetch, repeat }),
+        Box::new(ReadAfterWrite::<BB> { repeat }), Box::new(WriteThenReadSameIndices::<BB> { repeat }),
+    ];
+    for (r, w) in [(100, 0), (95, 5), (90, 10), (80, 20), (70, 30), (50, 50), (30, 70), (10, 90), (5, 95), (0, 100)] {
+        v.push(Box::new(Mixed::<BB>::new(r, w, repeat)));
+    }
+    v.extend([
+        Box::new(MixedBursty::<BB> { repeat }) as Box<dyn Scenario>,
+        Box::new(AdversarialHotspot::<BB>), Box::new(AdversarialHotspotRead::<BB>), Box::new(AdversarialHotspotMixed::<BB>),
+        Box::new(AdversarialColdspot::<BB>),
+        Box::new(Stride::<BB>::new(8)), Box::new(Stride::<BB>::new(64)),
+        Box::new(PointerChase::<BB>), Box::new(ScatterWrite::<BB>), Box::new(ScatterGather::<BB>),
+        Box::new(CacheThrash::<BB>::new(4)), Box::new(DependentReadChain::<BB>),
+        Box::new(ReadStrideDep::<BB>::new(8)), Box::new(ReadStrideDep::<BB>::new(64)),
+        Box::new(SortThenSearch::<BB> { queries: opts.sort_queries }), Box::new(FilterRetain::<BB>), Box::new(Dedup::<BB>),
+        Box::new(ChannelPipeline::<BB>),
+        #[cfg(feature = "rayon")]
+        Box::new(ParallelWriteSequential::<BB>),
+        #[cfg(feature = "rayon")]
+        Box::new(ParallelRandomWrite::<BB>),
+        #[cfg(feature = "rayon")]
+        Box::new(FalseSharing::<BB> { padded: false }),
+        #[cfg(feature = "rayon")]
+        Box::new(FalseSharing::<BB> { padded: true }),
+        #[cfg(feature = "rayon")]
+        Box::new(ConcurrentReaders::<BB>),
+    ]);
+    v.extend(CUSTOM_SCENARIOS.iter().map(|&(name, f)| Box::new(Registered { name, f }) as Box<dyn Scenario>));
+    v
+}
+
+// Members of the parameterised families that are not in the default list, e.g. STRIDE_S16.
+fn family_scenario(name: &str, opts: &ScenarioOpts) -> Option<Box<dyn Scenario>> {
+    if opts.no_black_box { family_member::<false>(name, opts) } else { family_member::<true>(name, opts) }
+}
+
+fn family_member<const BB: bool>(name: &str, opts: &ScenarioOpts) -> Option<Box<dyn Scenario>> {
+    let positive = |s: &str| s.parse::<usize>().ok().filter(|&k| k > 0);
+    if let Some((r, w)) = name.strip_prefix("MIXED_R").and_then(|p| p.split_once('W')) {
+        // percentages of one op stream, so each is 0..=100 and they sum to 100
+        let pct = |s: &str| s.parse::<i32>().ok().filter(|p| (0..=100).contains(p));
+        let (r, w) = (pct(r)?, pct(w)?);
+        if r + w != 100 { return None; }
+        return Some(Box::new(Mixed::<BB>::new(r, w, opts.repeat_inner.max(1))));
+    }
+    if let Some(c) = name.strip_prefix("WRITE_SEQUENTIAL_CHUNKED_") { return Some(Box::new(WriteSequentialChunked::<BB>::new(positive(c)?))); }
+    if let Some(p) = name.strip_prefix("MEMSET_PARTIAL_") {
+        return Some(Box::new(MemsetPartial::<BB>::new(p.parse().ok().filter(|&p| p <= 100)?)));
+    }
+    if let Some(k) = name.strip_prefix("CACHE_THRASH_") { return Some(Box::new(CacheThrash::<BB>::new(positive(k)?))); }
+    if let Some(k) = name.strip_prefix("READ_STRIDE_DEP_") { return Some(Box::new(ReadStrideDep::<BB>::new(positive(k)?))); }
+    if let Some(k) = name.strip_prefix("ROTATE_") { return Some(Box::new(Rotate::<BB>::new(Some(positive(k)?)))); }
+    if let Some(k) = name.strip_prefix("STRIDE_S") { return Some(Box::new(Stride::<BB>::new(positive(k)?))); }
+    None
+}
+
+// `MIXED_` or `MIXED_*` selects every scenario with that prefix; anything else must match exactly.
+fn scenario_matches(name: &str, pat: &str) -> bool {
+    if let Some(prefix) = pat.strip_suffix('*') { return name.starts_with(prefix); }
+    if pat.ends_with('_') { return name.starts_with(pat); }
+    name == pat
+}
+
+pub(crate) fn select_scenarios(filter: &str, opts: &ScenarioOpts) -> Result<Vec<Box<dyn Scenario>>, String> {
+    let pats: Vec<&str> = filter.split(',').map(str::trim).filter(|p| !p.is_empty()).collect();
+    let all = all_scenarios(opts);
+    if pats.is_empty() { return Ok(all); }
+    let mut out: Vec<Box<dyn Scenario>> = all.into_iter().filter(|s| pats.iter().any(|p| scenario_matches(s.name(), p))).collect();
+    // exact names outside the default list (e.g. STRIDE_S16) are built from their family; a prefix
+    // that selects nothing is as much a typo as an unknown name
+    for p in &pats {
+        if p.ends_with('*') || p.ends_with('_') {
+            if !out.iter().any(|s| scenario_matches(s.name(), p)) {
+                return Err(format!("no scenario matches {:?} in --scenarios", p));
+            }
+            continue;
+        }
+        if out.iter().any(|s| s.name() == *p) { continue; }
+        out.push(family_scenario(p, opts).ok_or_else(|| format!("unknown scenario {:?} in --scenarios", p))?);
+    }
+    Ok(out)
+}